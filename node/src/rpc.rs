@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use parachain_polkadex_runtime::{opaque::Block, AccountId, Balance, Index as Nonce};
+use parachain_polkadex_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index as Nonce};
 
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -44,15 +44,18 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: xcm_helper_rpc::XcmHelperRuntimeApi<Block, AccountId, BlockNumber>,
 	P: TransactionPool + Sync + Send + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
+	use xcm_helper_rpc::{XcmHelper, XcmHelperApiServer};
 
 	let mut module = RpcExtension::new(());
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(XcmHelper::new(client).into_rpc())?;
 	Ok(module)
 }
@@ -70,28 +70,119 @@ benchmarks! {
 		assert!(active_members.contains(&pending_council_member));
 	}
 
-	delete_transaction {
-		let b in 1 .. 1000;
-		let council_member: T::AccountId = account("mem1", b, SEED);
-		let mut active_council_member = <ActiveCouncilMembers<T>>::get();
-		active_council_member.try_push(council_member.clone()).unwrap();
-		<ActiveCouncilMembers<T>>::put(active_council_member);
-		// Add Pending Withdrawal
-		let block_no: T::BlockNumber = 100u64.saturated_into();
-		let pending_withdrawal = Withdraw {
-			id: Vec::new(),
-			asset_id: 0,
-			amount: 0,
-			destination: vec![],
-			is_blocked: false,
-			extra: vec![]
-		};
-		xcm_helper::Pallet::<T>::insert_pending_withdrawal(block_no, pending_withdrawal);
-	}: _(RawOrigin::Signed(council_member), block_no, 0u32)
+	retry_failed_withdrawal {
+		let (council_member, block_no, id) = setup_failed_withdrawal::<T>();
+	}: _(RawOrigin::Signed(council_member), id)
+	verify {
+		assert!(xcm_helper::Pallet::<T>::get_failed_withdrawals(block_no).is_empty());
+	}
+
+	refund_failed_withdrawal {
+		let (council_member, block_no, id) = setup_failed_withdrawal::<T>();
+		let recipient: T::AccountId = account("mem2", 1, SEED);
+	}: _(RawOrigin::Signed(council_member), id, recipient)
 	verify {
-		let pending_withdrawal = xcm_helper::Pallet::<T>::get_pending_withdrawals(block_no).pop().unwrap();
+		assert!(xcm_helper::Pallet::<T>::get_failed_withdrawals(block_no).is_empty());
+	}
+
+	discard_failed_withdrawal {
+		let (council_member, block_no, id) = setup_failed_withdrawal::<T>();
+	}: _(RawOrigin::Signed(council_member), id)
+	verify {
+		assert!(xcm_helper::Pallet::<T>::get_failed_withdrawals(block_no).is_empty());
+	}
+
+	block_withdrawal {
+		let (council_member, block_no, id) = setup_pending_withdrawal::<T>(false);
+	}: _(RawOrigin::Signed(council_member), id)
+	verify {
+		let pending_withdrawal = xcm_helper::Pallet::<T>::get_pending_withdrawals(block_no).into_inner().pop().unwrap();
 		assert!(pending_withdrawal.is_blocked);
 	}
+
+	unblock_withdrawal {
+		let (council_member, block_no, id) = setup_pending_withdrawal::<T>(true);
+	}: _(RawOrigin::Signed(council_member), id)
+	verify {
+		let pending_withdrawal = xcm_helper::Pallet::<T>::get_pending_withdrawals(block_no).into_inner().pop().unwrap();
+		assert!(!pending_withdrawal.is_blocked);
+	}
+
+	release_held_withdrawal {
+		let (council_member, id) = setup_held_withdrawal::<T>();
+	}: _(RawOrigin::Signed(council_member), id.clone())
+	verify {
+		assert!(xcm_helper::Pallet::<T>::get_pending_withdrawal_by_id(&id).is_some());
+	}
+
+	pause_bridge {
+		let council_member = setup_council_member::<T>();
+	}: _(RawOrigin::Signed(council_member), xcm_helper::BridgeDirection::Outbound)
+	verify {
+		assert!(xcm_helper::Pallet::<T>::is_outbound_paused());
+	}
+}
+
+fn setup_council_member<T: Config>() -> T::AccountId {
+	let council_member: T::AccountId = account("mem1", 1, SEED);
+	let mut active_council_member = <ActiveCouncilMembers<T>>::get();
+	active_council_member.try_push(council_member.clone()).unwrap();
+	<ActiveCouncilMembers<T>>::put(active_council_member);
+	council_member
+}
+
+fn setup_held_withdrawal<T: Config>() -> (T::AccountId, Vec<u8>) {
+	let council_member = setup_council_member::<T>();
+	let id = vec![1; 32];
+	let held_withdrawal = Withdraw {
+		id: id.clone(),
+		asset_id: 1,
+		amount: 1_000_000_000_000,
+		destination: vec![],
+		is_blocked: false,
+		extra: vec![],
+	};
+	xcm_helper::Pallet::<T>::insert_held_withdrawal(held_withdrawal).unwrap();
+	(council_member, id)
+}
+
+fn setup_pending_withdrawal<T: Config>(
+	is_blocked: bool,
+) -> (T::AccountId, T::BlockNumber, Vec<u8>) {
+	let council_member = setup_council_member::<T>();
+	let block_no: T::BlockNumber = 100u64.saturated_into();
+	let id = vec![1; 32];
+	let pending_withdrawal = Withdraw {
+		id: id.clone(),
+		asset_id: 1,
+		amount: 1_000_000_000_000,
+		destination: vec![],
+		is_blocked,
+		extra: vec![],
+	};
+	xcm_helper::Pallet::<T>::insert_pending_withdrawal(block_no, pending_withdrawal).unwrap();
+	(council_member, block_no, id)
+}
+
+fn setup_failed_withdrawal<T: Config>() -> (T::AccountId, T::BlockNumber, Vec<u8>) {
+	let council_member = setup_council_member::<T>();
+	let block_no: T::BlockNumber = 100u64.saturated_into();
+	let id = vec![1; 32];
+	let failed_withdrawal = Withdraw {
+		id: id.clone(),
+		asset_id: 1,
+		amount: 1_000_000_000_000,
+		destination: vec![],
+		is_blocked: false,
+		extra: vec![],
+	};
+	xcm_helper::Pallet::<T>::insert_failed_withdrawal(
+		block_no,
+		failed_withdrawal,
+		xcm_helper::WithdrawalFailureReason::BlockedByCouncil,
+	)
+	.unwrap();
+	(council_member, block_no, id)
 }
 
 #[cfg(test)]
@@ -37,7 +37,14 @@
 //! - `add_member` - Adds member to council.
 //! - `remove_member` - Removes member from council.
 //! - `claim_membership` - Converts Council member status from pending to Active.
-//! - `delete_transaction` - Blocks withdrawal request.
+//! - `block_withdrawal` - Blocks withdrawal request by Thea withdrawal id.
+//! - `unblock_withdrawal` - Votes to unblock withdrawal request by Thea withdrawal id.
+//! - `retry_failed_withdrawal` - Votes to re-queue failed withdrawal.
+//! - `refund_failed_withdrawal` - Votes to refund failed withdrawal back to Thea.
+//! - `discard_failed_withdrawal` - Votes to permanently remove failed withdrawal.
+//! - `release_held_withdrawal` - Votes to release held large withdrawal for execution.
+//! - `pause_bridge` - Votes to pause bridge in given direction, needs fewer votes than other
+//!   proposals. Bridge can be unpaused only by governance.
 //!
 //! ### Public Inspection functions - Immutable (getters)
 //! - `is_council_member` - Checks if given member is council member.
@@ -51,7 +58,8 @@
 //! - `NewPendingMemberAdded` - New Pending Member added.
 //! - `NewActiveMemberAdded` - New Active Member added.
 //! - `MemberRemoved` - Council Member removed.
-//! - `TransactionDeleted` - Transaction blocked.
+//! - `WithdrawalBlocked` - Withdrawal blocked by id.
+//! - `WithdrawalUnblocked` - Withdrawal unblocked by id.
 pub use pallet::*;
 
 #[cfg(test)]
@@ -71,11 +79,25 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 	use sp_runtime::{Percent, SaturatedConversion};
+	use sp_std::vec::Vec;
+	use xcm_helper::{BridgeDirection, WithdrawalId};
 
-	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone)]
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone)]
 	pub enum Proposal<AccountId> {
 		AddNewMember(AccountId),
 		RemoveExistingMember(AccountId),
+		/// Re-queue failed withdrawal [withdrawal_id]
+		RetryFailedWithdrawal(WithdrawalId),
+		/// Refund failed withdrawal to Thea [withdrawal_id, recipient]
+		RefundFailedWithdrawal(WithdrawalId, AccountId),
+		/// Discard failed withdrawal [withdrawal_id]
+		DiscardFailedWithdrawal(WithdrawalId),
+		/// Unblock withdrawal blocked by council member [withdrawal_id]
+		UnblockWithdrawal(WithdrawalId),
+		/// Release held large withdrawal [withdrawal_id]
+		ReleaseHeldWithdrawal(WithdrawalId),
+		/// Pause bridge [direction]
+		PauseBridge(BridgeDirection),
 	}
 
 	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Eq, PartialEq, Debug)]
@@ -85,7 +107,13 @@ pub mod pallet {
 		fn add_member(b: u32) -> Weight;
 		fn remove_member(_b: u32) -> Weight;
 		fn claim_membership(b: u32) -> Weight;
-		fn delete_transaction(_b: u32) -> Weight;
+		fn retry_failed_withdrawal() -> Weight;
+		fn refund_failed_withdrawal() -> Weight;
+		fn discard_failed_withdrawal() -> Weight;
+		fn block_withdrawal() -> Weight;
+		fn unblock_withdrawal() -> Weight;
+		fn release_held_withdrawal() -> Weight;
+		fn pause_bridge() -> Weight;
 	}
 
 	#[pallet::pallet]
@@ -141,10 +169,12 @@ pub mod pallet {
 		NewActiveMemberAdded(T::AccountId),
 		/// Member removed [member]
 		MemberRemoved(T::AccountId),
-		/// Transaction deleted
-		TransactionDeleted(u32),
 		/// Removed some unclaimed proposed council members
 		RetainPeriodExpiredForCouncilProposal(u32),
+		/// Withdrawal blocked [withdrawal_id]
+		WithdrawalBlocked(Vec<u8>),
+		/// Withdrawal unblocked [withdrawal_id]
+		WithdrawalUnblocked(Vec<u8>),
 	}
 
 	// Errors inform users that something went wrong.
@@ -221,25 +251,111 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Blocks malicious Pending Transaction.
+		/// Votes to re-queue failed withdrawal for execution.
 		///
 		/// # Parameters
 		///
-		/// * `block_no`: Block No which contains malicious transaction.
-		/// * `index`: Index of Malicious transaction in the list.
-		#[pallet::call_index(3)]
-		#[pallet::weight(T::TheaCouncilWeightInfo::delete_transaction(1))]
-		pub fn delete_transaction(
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::retry_failed_withdrawal())]
+		pub fn retry_failed_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			let id = Self::failed_withdrawal_id(id)?;
+			Self::evaluate_proposal(Proposal::RetryFailedWithdrawal(id), sender)
+		}
+
+		/// Votes to refund failed withdrawal back to Thea.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		/// * `recipient`: Account which will be credited on Thea side.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::refund_failed_withdrawal())]
+		pub fn refund_failed_withdrawal(
 			origin: OriginFor<T>,
-			block_no: T::BlockNumber,
-			index: u32,
+			id: Vec<u8>,
+			recipient: T::AccountId,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
-			xcm_helper::Pallet::<T>::block_by_ele(block_no, index)?;
-			Self::deposit_event(Event::<T>::TransactionDeleted(index));
+			let id = Self::failed_withdrawal_id(id)?;
+			Self::evaluate_proposal(Proposal::RefundFailedWithdrawal(id, recipient), sender)
+		}
+
+		/// Votes to permanently remove failed withdrawal.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::discard_failed_withdrawal())]
+		pub fn discard_failed_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			let id = Self::failed_withdrawal_id(id)?;
+			Self::evaluate_proposal(Proposal::DiscardFailedWithdrawal(id), sender)
+		}
+
+		/// Blocks malicious Pending Withdrawal by its Thea withdrawal id.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of malicious withdrawal.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::block_withdrawal())]
+		pub fn block_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			xcm_helper::Pallet::<T>::set_withdrawal_blocked(&id, true)?;
+			Self::deposit_event(Event::<T>::WithdrawalBlocked(id));
 			Ok(())
 		}
+
+		/// Votes to unblock Pending Withdrawal by its Thea withdrawal id. Any council member can
+		/// block a withdrawal, but lifting the block needs the votes of the council.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of blocked withdrawal.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::unblock_withdrawal())]
+		pub fn unblock_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			let id = WithdrawalId::try_from(id)
+				.map_err(|_| xcm_helper::Error::<T>::WithdrawalNotPending)?;
+			Self::evaluate_proposal(Proposal::UnblockWithdrawal(id), sender)
+		}
+
+		/// Votes to release held large withdrawal for execution.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of held withdrawal.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::release_held_withdrawal())]
+		pub fn release_held_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			let id = WithdrawalId::try_from(id)
+				.map_err(|_| xcm_helper::Error::<T>::WithdrawalNotHeld)?;
+			Self::evaluate_proposal(Proposal::ReleaseHeldWithdrawal(id), sender)
+		}
+
+		/// Votes to pause bridge in given direction.
+		///
+		/// # Parameters
+		///
+		/// * `direction`: Direction to pause.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::TheaCouncilWeightInfo::pause_bridge())]
+		pub fn pause_bridge(origin: OriginFor<T>, direction: BridgeDirection) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_council_member(&sender), Error::<T>::SenderNotCouncilMember);
+			Self::evaluate_proposal(Proposal::PauseBridge(direction), sender)
+		}
 	}
 
 	#[pallet::hooks]
@@ -298,13 +414,23 @@ pub mod pallet {
 			}
 		}
 
+		/// Pausing the bridge is an emergency measure, so a third of the council is enough.
+		pub(crate) fn get_expected_votes_for_pause() -> usize {
+			let total_active_council_size = <ActiveCouncilMembers<T>>::get().len();
+			let p = Percent::from_percent(34);
+			(p * total_active_council_size).max(1)
+		}
+
 		fn evaluate_proposal(
 			proposal: Proposal<T::AccountId>,
 			sender: T::AccountId,
 		) -> DispatchResult {
 			let current_votes =
 				|votes: &BoundedVec<Voted<T::AccountId>, ConstU32<10>>| -> usize { votes.len() };
-			let expected_votes = Self::get_expected_votes();
+			let expected_votes = match proposal {
+				Proposal::PauseBridge(_) => Self::get_expected_votes_for_pause(),
+				_ => Self::get_expected_votes(),
+			};
 			let mut remove_proposal = false;
 			<Proposals<T>>::try_mutate(proposal.clone(), |votes| {
 				ensure!(!votes.contains(&Voted(sender.clone())), Error::<T>::SenderAlreadyVoted);
@@ -332,9 +458,32 @@ pub mod pallet {
 				Proposal::AddNewMember(new_member) => Self::execute_add_member(new_member),
 				Proposal::RemoveExistingMember(member_to_be_removed) =>
 					Self::execute_remove_member(member_to_be_removed),
+				Proposal::RetryFailedWithdrawal(id) =>
+					xcm_helper::Pallet::<T>::do_retry_failed_withdrawal(&id),
+				Proposal::RefundFailedWithdrawal(id, recipient) =>
+					xcm_helper::Pallet::<T>::do_refund_failed_withdrawal(&id, recipient),
+				Proposal::DiscardFailedWithdrawal(id) =>
+					xcm_helper::Pallet::<T>::do_discard_failed_withdrawal(&id),
+				Proposal::UnblockWithdrawal(id) => {
+					xcm_helper::Pallet::<T>::set_withdrawal_blocked(&id, false)?;
+					Self::deposit_event(Event::<T>::WithdrawalUnblocked(id.into_inner()));
+					Ok(())
+				},
+				Proposal::ReleaseHeldWithdrawal(id) =>
+					xcm_helper::Pallet::<T>::do_release_held_withdrawal(&id),
+				Proposal::PauseBridge(direction) => {
+					xcm_helper::Pallet::<T>::do_pause_bridge(direction);
+					Ok(())
+				},
 			}
 		}
 
+		/// Failed withdrawals are addressed by Thea withdrawal id, which is bounded in storage.
+		fn failed_withdrawal_id(id: Vec<u8>) -> Result<WithdrawalId, DispatchError> {
+			WithdrawalId::try_from(id)
+				.map_err(|_| xcm_helper::Error::<T>::WithdrawalNotFailed.into())
+		}
+
 		fn execute_add_member(new_member: T::AccountId) -> DispatchResult {
 			let mut pending_council_member = <PendingCouncilMembers<T>>::get();
 			pending_council_member
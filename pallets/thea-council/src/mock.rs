@@ -17,6 +17,7 @@ use crate as thea_council;
 use frame_support::{
 	parameter_types,
 	traits::{ConstU16, ConstU64},
+	weights::Weight,
 };
 use frame_system as system;
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key};
@@ -132,8 +133,12 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 	pub const AssetHandlerPalletId: PalletId = PalletId(*b"XcmHandl");
 	pub const WithdrawalExecutionBlockDiff: u32 = 1000;
-	pub ParachainId: u32 = 2040;
 	pub NativeAssetId: u128 = 100;
+	pub const WithdrawalExecutionWeightLimit: Weight = Weight::MAX;
+	pub const MaxPendingWithdrawalsPerBlock: u32 = 100;
+	pub const MaxFailedWithdrawalsPerBlock: u32 = 100;
+	pub const MaxWhitelistedTokens: u32 = 50;
+	pub const FeePotAccount: u64 = 500;
 }
 
 impl xcm_helper::Config for Test {
@@ -146,9 +151,14 @@ impl xcm_helper::Config for Test {
 	type Executor = TheaMessageHandler;
 	type AssetHandlerPalletId = AssetHandlerPalletId;
 	type WithdrawalExecutionBlockDiff = WithdrawalExecutionBlockDiff;
-	type ParachainId = ParachainId;
 	type SubstrateNetworkId = frame_support::traits::ConstU8<0>;
 	type NativeAssetId = NativeAssetId;
+	type WithdrawalExecutionWeightLimit = WithdrawalExecutionWeightLimit;
+	type MaxPendingWithdrawalsPerBlock = MaxPendingWithdrawalsPerBlock;
+	type MaxFailedWithdrawalsPerBlock = MaxFailedWithdrawalsPerBlock;
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+	type Swap = ();
+	type FeePotAccount = FeePotAccount;
 	type WeightInfo = xcm_helper::weights::WeightInfo<Test>;
 }
 
@@ -19,6 +19,8 @@ use crate::{
 use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use sp_core::{bounded::BoundedVec, ConstU32};
 use sp_runtime::SaturatedConversion;
+use thea_primitives::types::Withdraw;
+use xcm_helper::{BoundedWithdraw, BridgeDirection, WithdrawalFailureReason};
 
 #[test]
 fn test_add_member_returns_ok() {
@@ -185,6 +187,222 @@ fn get_expected_votes_test() {
 	})
 }
 
+#[test]
+fn test_retry_failed_withdrawal_with_council_votes_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		System::set_block_number(1);
+		assert_ok!(XcmHnadler::insert_failed_withdrawal(
+			1,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(TheaCouncil::retry_failed_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			vec![1; 32]
+		));
+		// Not executed until threshold is reached
+		assert_eq!(XcmHnadler::get_failed_withdrawals(1).len(), 1);
+		assert_ok!(TheaCouncil::retry_failed_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			vec![1; 32]
+		));
+		assert!(XcmHnadler::get_failed_withdrawals(1).is_empty());
+		assert_eq!(
+			XcmHnadler::get_pending_withdrawals(2).into_inner(),
+			vec![BoundedWithdraw::try_from(get_failed_withdrawal()).unwrap()]
+		);
+		assert!(!<Proposals<Test>>::contains_key(Proposal::RetryFailedWithdrawal(
+			BoundedVec::truncate_from(vec![1; 32])
+		)));
+	})
+}
+
+#[test]
+fn test_failed_withdrawal_votes_are_kept_when_other_failed_withdrawal_is_removed() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		System::set_block_number(1);
+		let mut second_withdrawal = get_failed_withdrawal();
+		second_withdrawal.id = vec![2; 32];
+		for withdrawal in [get_failed_withdrawal(), second_withdrawal.clone()] {
+			assert_ok!(XcmHnadler::insert_failed_withdrawal(
+				1,
+				withdrawal,
+				WithdrawalFailureReason::BlockedByCouncil,
+			));
+		}
+		assert_ok!(TheaCouncil::retry_failed_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			vec![2; 32]
+		));
+		// Withdrawal stored before the voted one is removed in the meantime
+		assert_ok!(XcmHnadler::discard_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32]));
+		assert_ok!(TheaCouncil::retry_failed_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			vec![2; 32]
+		));
+		assert!(XcmHnadler::get_failed_withdrawals(1).is_empty());
+		assert_eq!(
+			XcmHnadler::get_pending_withdrawals(2).into_inner(),
+			vec![BoundedWithdraw::try_from(second_withdrawal).unwrap()]
+		);
+	})
+}
+
+#[test]
+fn test_refund_failed_withdrawal_with_council_votes_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		assert_ok!(XcmHnadler::insert_failed_withdrawal(
+			1,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(TheaCouncil::refund_failed_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			vec![1; 32],
+			4
+		));
+		assert_ok!(TheaCouncil::refund_failed_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			vec![1; 32],
+			4
+		));
+		assert!(XcmHnadler::get_failed_withdrawals(1).is_empty());
+	})
+}
+
+#[test]
+fn test_discard_failed_withdrawal_with_council_votes_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		assert_ok!(XcmHnadler::insert_failed_withdrawal(
+			1,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(TheaCouncil::discard_failed_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			vec![1; 32]
+		));
+		assert_ok!(TheaCouncil::discard_failed_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			vec![1; 32]
+		));
+		assert!(XcmHnadler::get_failed_withdrawals(1).is_empty());
+	})
+}
+
+#[test]
+fn test_discard_failed_withdrawal_with_non_council_member_returns_sender_not_council_member() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XcmHnadler::insert_failed_withdrawal(
+			1,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_noop!(
+			TheaCouncil::discard_failed_withdrawal(RuntimeOrigin::signed(4), vec![1; 32]),
+			Error::<Test>::SenderNotCouncilMember
+		);
+	})
+}
+
+#[test]
+fn test_block_withdrawal_and_unblock_withdrawal_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		let withdrawal = get_failed_withdrawal();
+		assert_ok!(XcmHnadler::insert_pending_withdrawal(100, withdrawal.clone()));
+		assert_ok!(TheaCouncil::block_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			withdrawal.id.clone()
+		));
+		assert!(XcmHnadler::get_pending_withdrawals(100)[0].is_blocked);
+		// Single member can't lift the block
+		assert_ok!(TheaCouncil::unblock_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			withdrawal.id.clone()
+		));
+		assert!(XcmHnadler::get_pending_withdrawals(100)[0].is_blocked);
+		assert_noop!(
+			TheaCouncil::unblock_withdrawal(
+				RuntimeOrigin::signed(first_council_member),
+				withdrawal.id.clone()
+			),
+			Error::<Test>::SenderAlreadyVoted
+		);
+		assert_ok!(TheaCouncil::unblock_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			withdrawal.id.clone()
+		));
+		assert!(!XcmHnadler::get_pending_withdrawals(100)[0].is_blocked);
+		assert!(!<Proposals<Test>>::contains_key(Proposal::UnblockWithdrawal(
+			BoundedVec::truncate_from(withdrawal.id)
+		)));
+	})
+}
+
+#[test]
+fn test_block_withdrawal_with_unknown_id_returns_withdrawal_not_pending() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, _, _) = get_council_members();
+		assert_noop!(
+			TheaCouncil::block_withdrawal(RuntimeOrigin::signed(first_council_member), vec![1; 32]),
+			xcm_helper::Error::<Test>::WithdrawalNotPending
+		);
+	})
+}
+
+#[test]
+fn test_release_held_withdrawal_with_council_votes_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, second_council_member, _) = get_council_members();
+		System::set_block_number(1);
+		let withdrawal = get_failed_withdrawal();
+		assert_ok!(XcmHnadler::insert_held_withdrawal(withdrawal.clone()));
+		assert_ok!(TheaCouncil::release_held_withdrawal(
+			RuntimeOrigin::signed(first_council_member),
+			withdrawal.id.clone()
+		));
+		// Not released until threshold is reached
+		assert!(XcmHnadler::get_pending_withdrawal_by_id(&withdrawal.id).is_none());
+		assert_ok!(TheaCouncil::release_held_withdrawal(
+			RuntimeOrigin::signed(second_council_member),
+			withdrawal.id.clone()
+		));
+		assert_eq!(XcmHnadler::get_pending_withdrawal_by_id(&withdrawal.id), Some((2, withdrawal)));
+	})
+}
+
+#[test]
+fn test_pause_bridge_with_single_council_vote_returns_ok() {
+	new_test_ext().execute_with(|| {
+		setup_council_members();
+		let (first_council_member, _, _) = get_council_members();
+		// A third of the council is enough to pause the bridge
+		assert_ok!(TheaCouncil::pause_bridge(
+			RuntimeOrigin::signed(first_council_member),
+			BridgeDirection::Outbound
+		));
+		assert!(XcmHnadler::is_outbound_paused());
+		assert!(!XcmHnadler::is_inbound_paused());
+		assert!(!<Proposals<Test>>::contains_key(Proposal::PauseBridge(BridgeDirection::Outbound)));
+		assert_noop!(
+			TheaCouncil::pause_bridge(RuntimeOrigin::signed(4), BridgeDirection::Inbound),
+			Error::<Test>::SenderNotCouncilMember
+		);
+	})
+}
+
 fn setup_council_members() {
 	let (first_council_member, second_council_member, third_council_member) = get_council_members();
 	let council = BoundedVec::try_from(vec![
@@ -202,3 +420,14 @@ fn get_council_members() -> (u64, u64, u64) {
 	let third_council_member = 3;
 	(first_council_member, second_council_member, third_council_member)
 }
+
+fn get_failed_withdrawal() -> Withdraw {
+	Withdraw {
+		id: vec![1; 32],
+		asset_id: 1,
+		amount: 1_000_000_000_000,
+		destination: vec![],
+		is_blocked: false,
+		extra: vec![],
+	}
+}
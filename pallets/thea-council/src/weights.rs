@@ -13,15 +13,17 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
 // GNU General Public License for more details.
 
-//! Autogenerated weights for `thea_council`
+//! Weights for `thea_council`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-06-28, STEPS: `100`, REPEAT: `200`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Ubuntu-2204-jammy-amd64-base`, CPU: `Intel(R) Core(TM) i7-7700 CPU @ 3.60GHz`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 1024
+//! PROVISIONAL ESTIMATES, NOT BENCHMARK OUTPUT.
+//! Only `add_member`, `remove_member` and `claim_membership` were measured with the Substrate
+//! benchmark CLI (DATE: 2023-06-28, STEPS: `100`, REPEAT: `200`,
+//! CPU: `Intel(R) Core(TM) i7-7700 CPU @ 3.60GHz`).
+//! All other weights, and the storage reads, writes and proof sizes changed by bounded storage,
+//! are hand-written estimates derived from comparable extrinsics. Regenerate this file with the
+//! command below before relying on these figures.
 
-// Executed Command:
+// Command to regenerate:
 // ./parachain-polkadex-node
 // benchmark
 // pallet
@@ -103,18 +105,140 @@ impl<T: frame_system::Config> crate::TheaCouncilWeightInfo for WeightInfo<T> {
     }
     /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
     /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
     /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
-    /// Proof Skipped: XcmHelper PendingWithdrawals (max_values: None, max_size: None, mode: Measured)
-    /// The range of component `b` is `[1, 1000]`.
-    /// The range of component `b` is `[1, 1000]`.
-    fn delete_transaction(_b: u32, ) -> Weight {
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalIndex (r:0 w:1)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn retry_failed_withdrawal() -> Weight {
         // Proof Size summary in bytes:
-        //  Measured:  `256`
-        //  Estimated: `3721`
-        // Minimum execution time: 12_884_000 picoseconds.
-        Weight::from_parts(13_467_259, 0)
-            .saturating_add(Weight::from_parts(0, 3721))
-            .saturating_add(T::DbWeight::get().reads(2))
+        //  Measured:  `339`
+        //  Estimated: `632487`
+        // Minimum execution time: 21_406_000 picoseconds.
+        Weight::from_parts(22_113_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    /// Storage: XcmHelper RandomnessNonce (r:1 w:1)
+    /// Proof: XcmHelper RandomnessNonce (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: TheaMessageHandler OutgoingNonce (r:1 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingNonce (max_values: None, max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler OutgoingMessages (r:0 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingMessages (max_values: None, max_size: None, mode: Measured)
+    fn refund_failed_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `412`
+        //  Estimated: `632487`
+        // Minimum execution time: 33_975_000 picoseconds.
+        Weight::from_parts(34_802_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn discard_failed_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `339`
+        //  Estimated: `632487`
+        // Minimum execution time: 17_288_000 picoseconds.
+        Weight::from_parts(17_934_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: XcmHelper HeldWithdrawals (r:1 w:0)
+    /// Proof: XcmHelper HeldWithdrawals (max_values: None, max_size: Some(697), added: 3172, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalIndex (r:1 w:0)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: MaxEncodedLen)
+    fn block_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `312`
+        //  Estimated: `618487`
+        // Minimum execution time: 17_642_000 picoseconds.
+        Weight::from_parts(18_201_000, 0)
+            .saturating_add(Weight::from_parts(0, 618487))
+            .saturating_add(T::DbWeight::get().reads(4))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper HeldWithdrawals (r:1 w:0)
+    /// Proof: XcmHelper HeldWithdrawals (max_values: None, max_size: Some(697), added: 3172, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalIndex (r:1 w:0)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: MaxEncodedLen)
+    fn unblock_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `380`
+        //  Estimated: `618487`
+        // Minimum execution time: 22_598_000 picoseconds.
+        Weight::from_parts(23_137_000, 0)
+            .saturating_add(Weight::from_parts(0, 618487))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper HeldWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper HeldWithdrawals (max_values: None, max_size: Some(697), added: 3172, mode: MaxEncodedLen)
+    /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalIndex (r:0 w:1)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn release_held_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `412`
+        //  Estimated: `618487`
+        // Minimum execution time: 24_716_000 picoseconds.
+        Weight::from_parts(25_384_000, 0)
+            .saturating_add(Weight::from_parts(0, 618487))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    /// Storage: TheaCouncil ActiveCouncilMembers (r:1 w:0)
+    /// Proof: TheaCouncil ActiveCouncilMembers (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+    /// Storage: TheaCouncil Proposals (r:1 w:1)
+    /// Proof: TheaCouncil Proposals (max_values: None, max_size: Some(370), added: 2845, mode: MaxEncodedLen)
+    /// Storage: XcmHelper OutboundPaused (r:0 w:1)
+    /// Proof: XcmHelper OutboundPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn pause_bridge() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `168`
+        //  Estimated: `3835`
+        // Minimum execution time: 15_983_000 picoseconds.
+        Weight::from_parts(16_472_000, 0)
+            .saturating_add(Weight::from_parts(0, 3835))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
 }
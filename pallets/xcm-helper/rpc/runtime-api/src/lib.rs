@@ -0,0 +1,49 @@
+// This file is part of Polkadex.
+
+// Copyright (C) 2020-2023 Polkadex oü.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+//! Runtime API definition for XCM Helper pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+use thea_primitives::types::Withdraw;
+use xcm_helper::FailedWithdrawal;
+
+sp_api::decl_runtime_apis! {
+	pub trait XcmHelperApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns withdrawals scheduled for execution in given block
+		fn pending_withdrawals_by_block(block_no: BlockNumber) -> Vec<Withdraw>;
+		/// Returns pending withdrawals destined to given account along with their execution block,
+		/// read from at most `limit` blocks following `start_key`, and key of the next page
+		fn pending_withdrawals_by_recipient(
+			recipient: AccountId,
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(BlockNumber, Withdraw)>, Option<Vec<u8>>);
+		/// Returns pending withdrawal with given Thea withdrawal id along with its execution block
+		fn pending_withdrawal_by_id(id: Vec<u8>) -> Option<(BlockNumber, Withdraw)>;
+		/// Returns failed withdrawals along with reason of failure, read from at most `limit`
+		/// blocks following `start_key`, and key of the next page
+		fn failed_withdrawals(
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<FailedWithdrawal<BlockNumber>>, Option<Vec<u8>>);
+	}
+}
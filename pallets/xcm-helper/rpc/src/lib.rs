@@ -0,0 +1,251 @@
+// This file is part of Polkadex.
+
+// Copyright (C) 2020-2023 Polkadex oü.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+//! RPC interface for XCM Helper pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::{Codec, Encode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use thea_primitives::types::Withdraw;
+use xcm_helper::FailedWithdrawal;
+
+pub use xcm_helper_runtime_api::XcmHelperApi as XcmHelperRuntimeApi;
+
+/// Pending withdrawal as returned by RPC
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWithdrawal<BlockNumber> {
+	/// Thea withdrawal id
+	pub id: Bytes,
+	pub asset_id: NumberOrHex,
+	pub amount: NumberOrHex,
+	/// SCALE encoded `VersionedMultiLocation`
+	pub destination: Bytes,
+	pub is_blocked: bool,
+	pub extra: Bytes,
+	/// Block in which withdrawal will be executed
+	pub execute_at: BlockNumber,
+}
+
+impl<BlockNumber> RpcWithdrawal<BlockNumber> {
+	fn new(execute_at: BlockNumber, withdrawal: Withdraw) -> Self {
+		Self {
+			id: withdrawal.id.into(),
+			asset_id: withdrawal.asset_id.into(),
+			amount: withdrawal.amount.into(),
+			destination: withdrawal.destination.into(),
+			is_blocked: withdrawal.is_blocked,
+			extra: withdrawal.extra.into(),
+			execute_at,
+		}
+	}
+}
+
+/// Failed withdrawal as returned by RPC
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFailedWithdrawal<BlockNumber> {
+	/// Thea withdrawal id
+	pub id: Bytes,
+	pub asset_id: NumberOrHex,
+	pub amount: NumberOrHex,
+	/// SCALE encoded `VersionedMultiLocation`
+	pub destination: Bytes,
+	/// SCALE encoded `WithdrawalFailureReason`
+	pub reason: Bytes,
+	/// Block in which execution was attempted
+	pub attempted_at: BlockNumber,
+}
+
+impl<BlockNumber> From<FailedWithdrawal<BlockNumber>> for RpcFailedWithdrawal<BlockNumber> {
+	fn from(failed_withdrawal: FailedWithdrawal<BlockNumber>) -> Self {
+		let FailedWithdrawal { withdraw, reason, attempted_at } = failed_withdrawal;
+		Self {
+			id: withdraw.id.into_inner().into(),
+			asset_id: withdraw.asset_id.into(),
+			amount: withdraw.amount.into(),
+			destination: withdraw.destination.into_inner().into(),
+			reason: reason.encode().into(),
+			attempted_at,
+		}
+	}
+}
+
+/// Page of withdrawals as returned by RPC
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPage<T> {
+	pub items: Vec<T>,
+	/// Key to query the next page with, missing if there are no more pages
+	pub next_key: Option<Bytes>,
+}
+
+#[rpc(client, server)]
+pub trait XcmHelperApi<BlockHash, AccountId, BlockNumber> {
+	/// Returns withdrawals scheduled for execution in given block
+	#[method(name = "xcmHelper_pendingWithdrawalsByBlock")]
+	fn pending_withdrawals_by_block(
+		&self,
+		block_no: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<RpcWithdrawal<BlockNumber>>>;
+
+	/// Returns page of pending withdrawals destined to given account, read from at most `limit`
+	/// blocks following `start_key`
+	#[method(name = "xcmHelper_pendingWithdrawalsByRecipient")]
+	fn pending_withdrawals_by_recipient(
+		&self,
+		recipient: AccountId,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<RpcPage<RpcWithdrawal<BlockNumber>>>;
+
+	/// Returns pending withdrawal with given Thea withdrawal id
+	#[method(name = "xcmHelper_pendingWithdrawalById")]
+	fn pending_withdrawal_by_id(
+		&self,
+		id: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RpcWithdrawal<BlockNumber>>>;
+
+	/// Returns page of failed withdrawals along with reason of failure, read from at most `limit`
+	/// blocks following `start_key`
+	#[method(name = "xcmHelper_failedWithdrawals")]
+	fn failed_withdrawals(
+		&self,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<RpcPage<RpcFailedWithdrawal<BlockNumber>>>;
+}
+
+/// Provides RPC methods to query XCM Helper pallet state.
+pub struct XcmHelper<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XcmHelper<C, Block> {
+	/// Creates a new instance of the XcmHelper RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for runtime API call errors.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query XCM Helper state",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<C, Block, AccountId, BlockNumber>
+	XcmHelperApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for XcmHelper<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmHelperRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Copy + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn pending_withdrawals_by_block(
+		&self,
+		block_no: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RpcWithdrawal<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let withdrawals = api
+			.pending_withdrawals_by_block(at, block_no)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(withdrawals
+			.into_iter()
+			.map(|withdrawal| RpcWithdrawal::new(block_no, withdrawal))
+			.collect())
+	}
+
+	fn pending_withdrawals_by_recipient(
+		&self,
+		recipient: AccountId,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RpcPage<RpcWithdrawal<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (withdrawals, next_key) = api
+			.pending_withdrawals_by_recipient(
+				at,
+				recipient,
+				start_key.map(|key| key.to_vec()),
+				limit,
+			)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(RpcPage {
+			items: withdrawals
+				.into_iter()
+				.map(|(execute_at, withdrawal)| RpcWithdrawal::new(execute_at, withdrawal))
+				.collect(),
+			next_key: next_key.map(Into::into),
+		})
+	}
+
+	fn pending_withdrawal_by_id(
+		&self,
+		id: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RpcWithdrawal<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let withdrawal = api
+			.pending_withdrawal_by_id(at, id.to_vec())
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(withdrawal.map(|(execute_at, withdrawal)| RpcWithdrawal::new(execute_at, withdrawal)))
+	}
+
+	fn failed_withdrawals(
+		&self,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RpcPage<RpcFailedWithdrawal<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (failed_withdrawals, next_key) = api
+			.failed_withdrawals(at, start_key.map(|key| key.to_vec()), limit)
+			.map_err(runtime_error_into_rpc_err)?;
+		Ok(RpcPage {
+			items: failed_withdrawals.into_iter().map(Into::into).collect(),
+			next_key: next_key.map(Into::into),
+		})
+	}
+}
@@ -20,13 +20,23 @@ use crate::Pallet as XcmHelper;
 use frame_benchmarking::{account, benchmarks};
 use frame_support::{
 	sp_runtime::SaturatedConversion,
-	traits::fungible::{Inspect as NativeInspect, Mutate as NativeMutate},
+	traits::{
+		fungible::{Inspect as NativeInspect, Mutate as NativeMutate},
+		Hooks,
+	},
+	BoundedVec,
 };
 use frame_system::RawOrigin;
+use parity_scale_codec::Encode;
 
 use sp_core::Get;
-use sp_runtime::traits::AccountIdConversion;
-use xcm::latest::{AssetId, Junction, Junctions, MultiLocation};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128};
+use sp_std::vec;
+use thea_primitives::types::Withdraw;
+use xcm::{
+	latest::{AssetId, Junction, Junctions, MultiLocation},
+	VersionedMultiLocation,
+};
 const SEED: u32 = 0;
 
 benchmarks! {
@@ -49,7 +59,7 @@ benchmarks! {
 		let token: AssetId = AssetId::Concrete(asset_location);
 		let token_id = XcmHelper::<T>::generate_asset_id_for_parachain(token);
 		let mut whitelisted_tokens = <WhitelistedTokens<T>>::get();
-		whitelisted_tokens.push(token_id);
+		whitelisted_tokens.try_push(token_id).unwrap();
 		<WhitelistedTokens<T>>::put(whitelisted_tokens);
 	}: _(RawOrigin::Root, token)
 	verify {
@@ -60,13 +70,248 @@ benchmarks! {
 	transfer_fee {
 		let b in 1 .. 1000;
 		let pallet_account: T::AccountId = T::AssetHandlerPalletId::get().into_account_truncating();
-		let asset = T::NativeAssetId::get();
 		T::Currency::mint_into(&pallet_account, 2_000_000_000_000_000u128.saturated_into()).unwrap();
 		let recipeint: T::AccountId = account("mem1", b, SEED);
 	}: _(RawOrigin::Root, recipeint.clone())
 	verify {
 		assert_eq!(T::Currency::balance(&recipeint), 1999000000000000u128.saturated_into());
 	}
+
+	transfer_fee_from_pot {
+		let fee_pot = T::FeePotAccount::get();
+		let asset = T::NativeAssetId::get();
+		T::Currency::mint_into(&fee_pot, 2_000_000_000_000_000u128.saturated_into()).unwrap();
+		let recipeint: T::AccountId = account("mem1", 1, SEED);
+	}: _(RawOrigin::Root, asset.into(), recipeint.clone())
+	verify {
+		assert_eq!(T::Currency::balance(&recipeint), 1999000000000000u128.saturated_into());
+	}
+
+	retry_failed_withdrawal {
+		let block_no: T::BlockNumber = 1u64.saturated_into();
+		XcmHelper::<T>::insert_failed_withdrawal(block_no, failed_withdrawal(), WithdrawalFailureReason::BlockedByCouncil).unwrap();
+	}: _(RawOrigin::Root, vec![1; 32])
+	verify {
+		assert!(<FailedWithdrawals<T>>::get(block_no).is_empty());
+	}
+
+	refund_failed_withdrawal {
+		let block_no: T::BlockNumber = 1u64.saturated_into();
+		XcmHelper::<T>::insert_failed_withdrawal(block_no, failed_withdrawal(), WithdrawalFailureReason::BlockedByCouncil).unwrap();
+		let recipient: T::AccountId = account("mem1", 1, SEED);
+	}: _(RawOrigin::Root, vec![1; 32], recipient)
+	verify {
+		assert!(<FailedWithdrawals<T>>::get(block_no).is_empty());
+	}
+
+	discard_failed_withdrawal {
+		let block_no: T::BlockNumber = 1u64.saturated_into();
+		XcmHelper::<T>::insert_failed_withdrawal(block_no, failed_withdrawal(), WithdrawalFailureReason::BlockedByCouncil).unwrap();
+	}: _(RawOrigin::Root, vec![1; 32])
+	verify {
+		assert!(<FailedWithdrawals<T>>::get(block_no).is_empty());
+	}
+
+	// Worst case is a fungible withdrawal leaving through orml_xtokens to another chain
+	#[pov_mode = MaxEncodedLen {
+		XcmHelper::PendingWithdrawals: Measured,
+		XcmHelper::FailedWithdrawals: Measured
+	}]
+	execute_withdrawal {
+		let x: T::BlockNumber = 1u64.saturated_into();
+		let asset_location = AssetId::Concrete(MultiLocation::parent());
+		XcmHelper::<T>::whitelist_token(RawOrigin::Root.into(), asset_location).unwrap();
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset_location);
+		// Rate limited asset also records its usage
+		<AssetRateLimits<T>>::insert(
+			asset_id,
+			AssetRateLimit { window: 100u64.saturated_into(), max_amount: 1_000_000_000_000 },
+		);
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1u8; 32] }),
+		));
+		let withdrawal = Withdraw {
+			id: vec![1; 32],
+			asset_id,
+			amount: 1_000_000_000_000,
+			destination: destination.encode(),
+			is_blocked: false,
+			extra: vec![],
+		};
+		XcmHelper::<T>::insert_pending_withdrawal(x, withdrawal).unwrap();
+	}: {
+		XcmHelper::<T>::on_initialize(x);
+	} verify {
+		// Transfer itself only goes through with the runtime's XCM executor, so only check
+		// that the withdrawal has left the pending queue
+		assert!(<PendingWithdrawals<T>>::get(x).is_empty());
+		let id = WithdrawalId::try_from(vec![1u8; 32]).unwrap();
+		assert!(<WithdrawalIndex<T>>::get(id).is_none());
+	}
+
+	set_asset_withdrawal_config {
+		let config = AssetWithdrawalConfig {
+			delay: 10u64.saturated_into(),
+			large_withdrawal_threshold: Some(1_000_000_000_000),
+			large_withdrawal_delay: None,
+		};
+	}: _(RawOrigin::Root, 1, Some(config))
+	verify {
+		assert_eq!(XcmHelper::<T>::get_asset_withdrawal_config(1), Some(config));
+	}
+
+	set_asset_rate_limit {
+		let limit = AssetRateLimit { window: 100u64.saturated_into(), max_amount: 1_000_000_000_000 };
+	}: _(RawOrigin::Root, 1, Some(limit))
+	verify {
+		assert_eq!(XcmHelper::<T>::get_asset_rate_limit(1), Some(limit));
+	}
+
+	pause_bridge {
+	}: _(RawOrigin::Root, BridgeDirection::Outbound)
+	verify {
+		assert!(<OutboundPaused<T>>::get());
+	}
+
+	unpause_bridge {
+		<OutboundPaused<T>>::put(true);
+	}: _(RawOrigin::Root, BridgeDirection::Outbound)
+	verify {
+		assert!(!<OutboundPaused<T>>::get());
+	}
+
+	freeze_asset {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(<FrozenAssets<T>>::contains_key(asset_id));
+	}
+
+	unfreeze_asset {
+		let asset_id = 100u128;
+		<FrozenAssets<T>>::insert(asset_id, ());
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(!<FrozenAssets<T>>::contains_key(asset_id));
+	}
+
+	set_asset_metadata {
+		let pallet_account: T::AccountId = T::AssetHandlerPalletId::get().into_account_truncating();
+		T::Currency::mint_into(&pallet_account, 2_000_000_000_000_000u128.saturated_into()).unwrap();
+		let metadata = AssetMetadata {
+			name: BoundedVec::truncate_from(b"Polkadot".to_vec()),
+			symbol: BoundedVec::truncate_from(b"DOT".to_vec()),
+			decimals: 10,
+			min_balance: 1,
+			location: MultiLocation::parent(),
+		};
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(AssetId::Concrete(MultiLocation::parent()));
+	}: _(RawOrigin::Root, metadata.clone())
+	verify {
+		assert_eq!(<AssetRegistry<T>>::get(asset_id), Some(metadata));
+	}
+
+	add_asset_alias {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		let alias = AssetId::Concrete(MultiLocation::new(1, Junctions::X2(Junction::Parachain(1000), Junction::PalletInstance(50))));
+	}: _(RawOrigin::Root, asset_id, alias)
+	verify {
+		assert_eq!(<ParachainAssetIds<T>>::get(alias), Some(asset_id));
+	}
+
+	remove_asset_alias {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		let alias = AssetId::Concrete(MultiLocation::new(1, Junctions::X2(Junction::Parachain(1000), Junction::PalletInstance(50))));
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		<ParachainAssetIds<T>>::insert(alias, asset_id);
+	}: _(RawOrigin::Root, alias)
+	verify {
+		assert!(!<ParachainAssetIds<T>>::contains_key(alias));
+	}
+
+	set_canonical_asset_location {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		let alias = AssetId::Concrete(MultiLocation::new(1, Junctions::X2(Junction::Parachain(1000), Junction::PalletInstance(50))));
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		<ParachainAssetIds<T>>::insert(alias, asset_id);
+	}: _(RawOrigin::Root, asset_id, alias)
+	verify {
+		assert_eq!(<ParachainAssets<T>>::get(asset_id), Some(alias));
+	}
+
+	set_abstract_asset_location {
+		let asset = AssetId::Abstract([1; 32]);
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		let location = MultiLocation::new(1, Junctions::X2(Junction::Parachain(1000), Junction::GeneralIndex(1)));
+	}: _(RawOrigin::Root, asset_id, Some(location))
+	verify {
+		assert_eq!(<AbstractAssetLocations<T>>::get(asset_id), Some(location));
+	}
+
+	set_asset_non_fungible {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::GeneralIndex(1))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+	}: _(RawOrigin::Root, asset_id, true)
+	verify {
+		assert!(<NonFungibleAssets<T>>::contains_key(asset_id));
+	}
+
+	set_asset_price {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		let price = FixedU128::from_u32(2);
+	}: _(RawOrigin::Root, asset_id, Some(price))
+	verify {
+		assert_eq!(<AssetPrices<T>>::get(asset_id), Some(price));
+	}
+
+	set_asset_fee_rate {
+		let asset = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let asset_id = XcmHelper::<T>::generate_asset_id_for_parachain(asset);
+		<ParachainAssets<T>>::insert(asset_id, asset);
+		<ParachainAssetIds<T>>::insert(asset, asset_id);
+		let rate = AssetFeeRate { units_per_second: 1_000_000_000_000, units_per_pov_byte: 1_000 };
+	}: _(RawOrigin::Root, asset_id, Some(rate))
+	verify {
+		assert_eq!(<AssetFeeRates<T>>::get(asset_id), Some(rate));
+	}
+
+	refund_unmigrated_withdrawal {
+		let withdrawal = failed_withdrawal();
+		let key = sp_io::hashing::blake2_256(&withdrawal.id);
+		<UnmigratedWithdrawals<T>>::insert(key, withdrawal.clone());
+		let recipient: T::AccountId = account("mem1", 1, SEED);
+	}: _(RawOrigin::Root, withdrawal.id, recipient)
+	verify {
+		assert!(<UnmigratedWithdrawals<T>>::get(key).is_none());
+	}
+
+	refund_rejected_withdrawal {
+		let withdrawal = failed_withdrawal();
+		let key = sp_io::hashing::blake2_256(&withdrawal.id);
+		<RejectedWithdrawals<T>>::insert(key, vec![withdrawal.clone()]);
+		let recipient: T::AccountId = account("mem1", 1, SEED);
+	}: _(RawOrigin::Root, withdrawal.id, recipient)
+	verify {
+		assert!(<RejectedWithdrawals<T>>::get(key).is_empty());
+	}
+
 	// TODO: We need to adapt this benchmark to work in runtime context
 	// on_initialize {
 	// 	let x: T::BlockNumber = 1u64.saturated_into();
@@ -111,6 +356,17 @@ benchmarks! {
 	// }
 }
 
+fn failed_withdrawal() -> Withdraw {
+	Withdraw {
+		id: vec![1; 32],
+		asset_id: 1,
+		amount: 1_000_000_000_000,
+		destination: vec![],
+		is_blocked: false,
+		extra: vec![],
+	}
+}
+
 #[cfg(test)]
 use frame_benchmarking::impl_benchmark_test_suite;
 
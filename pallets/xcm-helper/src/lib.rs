@@ -37,6 +37,26 @@
 //!
 //! - **WithdrawalExecutionBlockDiff** Delays in Blocks after which Pending withdrawal will be executed.
 //!
+//! - **AssetWithdrawalConfig** Per asset withdrawal delay overriding `WithdrawalExecutionBlockDiff`,
+//!   withdrawals above the large withdrawal threshold get a longer delay or are held until
+//!   released by Thea Council.
+//!
+//! - **AssetRateLimit** Maximum amount of an asset withdrawn within a window of blocks. Window
+//!   starts with the first withdrawal after the previous window ended, withdrawals exceeding the
+//!   limit are deferred to the end of the window.
+//!
+//! - **WithdrawalExecutionWeightLimit** Maximum weight spent on executing withdrawals in a block,
+//!   withdrawals which don't fit are carried over to the next block.
+//!
+//! - **MaxPendingWithdrawalsPerBlock** Maximum number of withdrawals scheduled in a block, batches
+//!   from Thea which would exceed it are rejected.
+//!
+//! - **MaxFailedWithdrawalsPerBlock** Maximum number of failed withdrawals stored per block.
+//!
+//! - **MaxWhitelistedTokens** Maximum number of tokens which can be whitelisted.
+//!
+//! - **FeePotAccount** Account collecting XCM fees paid in foreign assets.
+//!
 //! - **ParachainAsset** Type using which native Parachain will identify assets from foregin Parachain.
 //!
 //! ### Implementations
@@ -45,6 +65,11 @@
 //!
 //! -[`TransactAsset`]: Used by XCM Executor to deposit, withdraw and transfer native/non-native asset on Native Chain.
 //! -[`AssetIdConverter`]: Converts Assets id from Multilocation Format to Local Asset Id and vice-versa.
+//! -[`AssetPriceProvider`]: Provides price of native token in assets used to pay XCM fees.
+//! -[`AssetFeeRateProvider`]: Provides fixed fee rates of XCM execution paid in assets.
+//! -[`NativeFeeSwapper`]: Quotes and swaps XCM fees paid in assets into native token using
+//!   [`Swap`] implementation provided by the runtime.
+//! -[`TakeRevenue`]: Deposits XCM fees collected by traders into the fee pot account.
 //!
 //! ## Interface
 //!
@@ -52,19 +77,51 @@
 //! - `withdraw_asset` - Transfers Assets from Polkadex Sovereign Account to Others on native/non-native parachains using XCMP.
 //! - `deposit_asset` - Generate Ingress Message for new Deposit.
 //! - `transfer_asset` - Transfers Asset from source account to destination account.
+//! - `transfer_fee` - Deprecated, transfers native XCM fees collected in the pallet account.
+//! - `transfer_fee_from_pot` - Transfers XCM fees collected in an asset out of the fee pot.
+//! - `retry_failed_withdrawal` - Re-queues a failed withdrawal for execution in the next block.
+//! - `refund_failed_withdrawal` - Refunds a failed withdrawal back to Thea.
+//! - `discard_failed_withdrawal` - Permanently removes a failed withdrawal.
+//! - `set_asset_withdrawal_config` - Sets withdrawal delays and large withdrawal threshold of an asset.
+//! - `set_asset_rate_limit` - Sets maximum amount of an asset withdrawn within a window.
+//! - `pause_bridge` - Pauses inbound deposits or outbound withdrawals.
+//! - `unpause_bridge` - Unpauses inbound deposits or outbound withdrawals.
+//! - `freeze_asset` - Freezes bridging of a single asset.
+//! - `unfreeze_asset` - Unfreezes bridging of a single asset.
+//! - `set_asset_metadata` - Registers name, symbol, decimals and min balance of an asset and
+//!   applies them to the underlying asset.
+//! - `add_asset_alias` - Registers alias location resolving to an existing asset.
+//! - `remove_asset_alias` - Removes alias location of an asset.
+//! - `set_canonical_asset_location` - Sets which of the asset locations is used for withdrawals.
+//! - `set_abstract_asset_location` - Sets location used for withdrawals of an abstract asset.
+//! - `set_asset_non_fungible` - Marks asset as a collection of non-fungible instances.
+//! - `set_asset_price` - Sets price of native token in asset used to charge XCM fees.
+//! - `set_asset_fee_rate` - Sets fixed fee rate of XCM execution paid in asset.
+//! - `refund_unmigrated_withdrawal` - Refunds a withdrawal which couldn't be migrated to bounded
+//!   storage back to Thea.
+//! - `refund_rejected_withdrawal` - Refunds a rejected withdrawal which couldn't be refunded
+//!   automatically back to Thea.
 //!
 //! ### Supported Origins
 //! - `AssetCreateUpdateOrigin` - Origin which has access to Create Asset.
 //!
 //! ### Public Functions
 //! - `handle_deposit` - Handles deposits from foreign chain.
-//! - `generate_asset_id_for_parachain` - Retrieves the existing asset ID for given assetid
-//!    or generates and stores a new asset ID.
-//! - `block_by_ele` - Blocks Transaction to be Executed.
+//! - `generate_asset_id_for_parachain` - Generates asset ID for given assetid without storing it.
+//! - `get_asset_id` - Returns asset ID of native or registered asset.
+//! - `canonicalise_asset` - Simplifies asset location against universal location of this chain.
+//! - `set_withdrawal_blocked` - Blocks or unblocks pending withdrawal by Thea withdrawal id.
 //! - `convert_asset_id_to_location` - Converts asset_id to XCM::MultiLocation.
 //! - `convert_location_to_asset_id` - Converts Multilocation to u128.
 //! - `insert_pending_withdrawal` - Stores provided withdraw in a "PendingWithdrawals" collectiom.
 //! - `multi_location_to_account_converter` - Resolves "AccountId" based on provided MultiLocatiom.
+//! - `get_pending_withdrawals_by_recipient` - Returns page of pending withdrawals destined to given
+//!   account.
+//! - `get_pending_withdrawal_by_id` - Returns pending withdrawal with given Thea withdrawal id.
+//! - `get_failed_withdrawals_page` - Returns page of failed withdrawals along with failure reasons.
+//! - `get_withdrawal_execution_block` - Returns execution block of withdrawal based on asset config.
+//! - `do_release_held_withdrawal` - Queues held large withdrawal for execution.
+//! - `do_pause_bridge` - Pauses bridge in given direction, used by Thea Council.
 //!
 //! ### Public Inspection functions - Immutable (accessors)
 //! - `get_pallet_account` - Returns Pallet Id.
@@ -75,9 +132,30 @@
 //!
 //! ### Storage Items
 //! - `PendingWithdrawals` - Stores all pending withdrawal.
-//! - `FailedWithdrawals` - Stores failed withdrawals which failed during execution.
-//! - `ParachainAssets` - Stores assets mapping from u128 asset to multi asset.
+//! - `FailedWithdrawals` - Stores failed withdrawals along with the reason of failure.
+//! - `FailedWithdrawalIndex` - Stores block of failed withdrawal by Thea withdrawal id.
+//! - `ParachainAssets` - Stores assets mapping from u128 asset to multi asset, registered by
+//!   governance while whitelisting tokens or setting asset metadata.
+//! - `ParachainAssetIds` - Stores assets mapping from canonical multi asset to u128 asset,
+//!   including alias locations of assets.
+//! - `AbstractAssetLocations` - Stores locations used for withdrawals of abstract assets.
 //! - `WhitelistedTokens` - Stores whitelisted Tokens.
+//! - `WithdrawalIndex` - Stores execution block of pending withdrawal by Thea withdrawal id.
+//! - `AssetWithdrawalConfigs` - Stores withdrawal delays configured per asset.
+//! - `HeldWithdrawals` - Stores large withdrawals waiting for release by Thea Council.
+//! - `AssetRateLimits` - Stores withdrawal rate limits configured per asset.
+//! - `WithdrawalUsages` - Stores amount withdrawn per rate limited asset in the current window.
+//! - `InboundPaused` - Inbound deposits are paused.
+//! - `OutboundPaused` - Outbound withdrawal execution is paused.
+//! - `WithdrawalBacklogStart` - First block of withdrawals left in place while outbound is paused.
+//! - `FrozenAssets` - Stores assets whose deposits are trapped and withdrawals are deferred.
+//! - `AssetRegistry` - Stores metadata and canonical location registered per asset.
+//! - `NonFungibleAssets` - Stores assets bridged as non-fungible tokens.
+//! - `AssetPrices` - Stores price of native token in assets accepted for XCM fees.
+//! - `AssetFeeRates` - Stores fixed fee rates of XCM execution paid in foreign assets.
+//! - `UnmigratedWithdrawals` - Stores withdrawals exceeding the size limits of bounded storage.
+//! - `UnmigratedWhitelistedTokens` - Stores whitelisted tokens exceeding the whitelist limit.
+//! - `RejectedWithdrawals` - Stores rejected withdrawals which couldn't be refunded automatically.
 //! -
 //! # Events
 //! - `AssetDeposited` - Asset Deposited from XCM.
@@ -86,20 +164,99 @@
 //! - `TokenWhitelistedForXcm` - Token Whitelisted For Xcm Token.
 //! - `XcmFeeTransferred` - Xcm Fee Transferred.
 //! - `NativeAssetIdMappingRegistered` - Native asset id mapping is registered.
+//! - `FailedWithdrawalRequeued` - Failed withdrawal is queued for execution again.
+//! - `FailedWithdrawalRefunded` - Failed withdrawal is refunded back to Thea.
+//! - `FailedWithdrawalDiscarded` - Failed withdrawal is removed permanently.
+//! - `WithdrawalQueued` - Withdrawal received from Thea is queued for execution.
+//! - `WithdrawalExecuted` - Withdrawal executed successfully.
+//! - `WithdrawalFailed` - Withdrawal execution failed.
+//! - `WithdrawalsRejected` - Batch of withdrawals from Thea is rejected as it can't be stored.
+//! - `RejectedWithdrawalsRefunded` - Rejected withdrawals are refunded back to Thea.
+//! - `RejectedWithdrawalsKept` - Rejected withdrawals which couldn't be refunded are kept for
+//!   refund by governance.
+//! - `RejectedWithdrawalRefunded` - Kept rejected withdrawal is refunded back to Thea.
+//! - `UnmigratedWithdrawalRefunded` - Withdrawal which couldn't be migrated is refunded back to
+//!   Thea.
+//! - `AssetWithdrawalConfigUpdated` - Withdrawal delays of asset are updated.
+//! - `WithdrawalHeld` - Large withdrawal is held until released by Thea Council.
+//! - `HeldWithdrawalReleased` - Held withdrawal is released for execution.
+//! - `AssetRateLimitUpdated` - Withdrawal rate limit of asset is updated.
+//! - `WithdrawalDeferred` - Withdrawal execution is postponed to a later block.
+//! - `BridgePaused` - Bridge is paused in given direction.
+//! - `BridgeUnpaused` - Bridge is unpaused in given direction.
+//! - `AssetFrozen` - Bridging of asset is frozen.
+//! - `AssetUnfrozen` - Bridging of asset is unfrozen.
+//! - `AssetMetadataUpdated` - Metadata of asset is registered or updated.
+//! - `AssetAliasAdded` - Alias location of asset is registered.
+//! - `AssetAliasRemoved` - Alias location of asset is removed.
+//! - `CanonicalAssetLocationUpdated` - Location used for withdrawals of asset is updated.
+//! - `AbstractAssetLocationUpdated` - Location used for withdrawals of abstract asset is updated.
+//! - `AssetFungibilityUpdated` - Asset is marked as fungible or non-fungible.
+//! - `AssetPriceUpdated` - Price of native token in asset is updated.
+//! - `AssetFeeRateUpdated` - Fixed fee rate of XCM execution paid in asset is updated.
+//! - `DepositTrapped` - Deposit is rejected and its assets are trapped, carries the `TrapCode`.
+//! - `XcmFeeSwapped` - XCM fee paid in asset is swapped into native token.
+//! - `XcmFeeCollected` - XCM fee is deposited into the fee pot.
+//!
+//! ### Deposits
+//! Extra data of every deposit sent to Thea is SCALE encoded `DepositExtra` carrying id and
+//! origin of the XCM message which delivered the asset, they are emitted in `AssetDeposited`
+//! event as well. It allows to correlate transfer on the source chain with the Thea deposit and
+//! the credit on Polkadex.
+//!
+//! ### Errors returned to XCM Executor
+//! `TransactAsset` implementation returns standard `XcmError` variants where they describe the
+//! failure, e.g. `AssetNotFound` for unknown assets and `FailedToTransactAsset` if balance can't
+//! be withdrawn or transferred. Deposits rejected by the bridge itself return `XcmError::Trap`
+//! with a [`TrapCode`], so the assets are trapped and can be claimed later:
+//!
+//! - `102` `TheaDepositFailed` - Thea rejected the deposit.
+//! - `103` `InboundPaused` - Inbound deposits are paused.
+//! - `104` `AssetFrozen` - Bridging of asset is frozen.
+//!
+//! ### Fee Swapping
+//! XCM trader can charge fees in any asset having an AMM pool with native token. Fee in native
+//! token is quoted through `Config::Swap` and the asset paid for it is swapped into native token
+//! at the end of XCM execution, native token received is kept in the fee pot along with other
+//! XCM fees. Assets without a pool are accepted only if they are whitelisted and have a price set
+//! by `set_asset_price`.
+//!
+//! ### Fee Pot
+//! XCM fees which traders hand over through `TakeRevenue` are credited to `Config::FeePotAccount`
+//! on this chain, they are not bridged to Thea like deposits from other chains. Fees which can't
+//! be credited, e.g. below minimum balance of the asset, are dropped. Governance sweeps any asset
+//! out of the fee pot using `transfer_fee_from_pot`. Foreign asset fees collected in the pallet
+//! account before the fee pot existed are moved into it by `migration::v6`.
+//!
+//! ### Abstract Assets
+//! Assets identified by `AssetId::Abstract` are registered by whitelisting them like any other
+//! asset and are deposited, withdrawn and transferred through `TransactAsset` using their
+//! abstract id. As XCM transfers need a concrete reserve location, withdrawals of an abstract
+//! asset are sent out as the concrete location set by `set_abstract_asset_location`, and fail
+//! with `UnknownAssetMapping` until it is set. If the foreign chain sends the asset back using
+//! that location, it should be registered as an alias of the asset as well.
+//!
+//! ### Non-Fungible Assets
+//! Collection of non-fungible tokens is whitelisted like any other asset using its location and
+//! marked by `set_asset_non_fungible`. Every instance deposited over XCM gets its own asset id,
+//! derived from the collection and the instance and recorded in `NonFungibleInstances`, and is
+//! sent to Thea as a deposit of one unit of that id, so instances never become fungible with
+//! each other. Instances stay in the sovereign account of this chain on the reserve chain.
+//! Withdrawals use the asset id of the instance with an amount of one and a destination on the
+//! reserve chain of the collection, the instance is withdrawn back to the beneficiary using
+//! `InitiateReserveWithdraw`. Instances are not minted locally, so they can't be transferred or
+//! withdrawn to an account on this chain.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
 pub use pallet::*;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-/// We allow for 0.5 of a second of compute with a 12 second average block time.
-const MAXIMUM_BLOCK_WEIGHT: Weight = Weight::from_parts(
-	WEIGHT_REF_TIME_PER_SECOND.saturating_div(2),
-	cumulus_primitives_core::relay_chain::MAX_POV_SIZE as u64,
-);
+pub mod migration;
+
+pub mod traders;
 
 #[cfg(test)]
 mod mock;
@@ -116,36 +273,47 @@ pub mod pallet {
 		log,
 		pallet_prelude::*,
 		sp_runtime::traits::AccountIdConversion,
+		storage::{with_transaction, PrefixIterator, TransactionOutcome},
 		traits::{
 			fungible::{Inspect as InspectNative, Mutate as MutateNative},
-			fungibles::Inspect,
+			fungibles::{metadata::Mutate as MutateMetadata, Inspect},
 			tokens::{Fortitude, Preservation},
 		},
+		weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 
+	use orml_traits::location::{Parse, Reserve};
 	use polkadex_primitives::Resolver;
 	use sp_core::sp_std;
-	use sp_runtime::{traits::Convert, SaturatedConversion};
+	use sp_runtime::{
+		traits::{Convert, One, Zero},
+		FixedU128, SaturatedConversion,
+	};
 
-	use crate::MAXIMUM_BLOCK_WEIGHT;
-	use sp_std::{boxed::Box, vec, vec::Vec};
+	use sp_std::{
+		boxed::Box,
+		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+		vec,
+		vec::Vec,
+	};
 	use thea_primitives::{
 		types::{Deposit, Withdraw},
 		Network, TheaIncomingExecutor, TheaOutgoingExecutor,
 	};
 	use xcm::{
 		latest::{
-			Error as XcmError, Fungibility, Junction, Junctions, MultiAsset, MultiAssets,
-			MultiLocation, XcmContext,
+			AssetInstance, Error as XcmError, ExecuteXcm, Fungibility, Instruction, Junction,
+			Junctions, MultiAsset, MultiAssetFilter, MultiAssets, MultiLocation, WildMultiAsset,
+			Xcm, XcmContext, XcmHash,
 		},
-		prelude::Parachain,
 		v3::AssetId,
 		VersionedMultiAssets, VersionedMultiLocation,
 	};
+	use xcm_builder::TakeRevenue;
 	use xcm_executor::{
-		traits::{Convert as MoreConvert, TransactAsset},
+		traits::{Convert as MoreConvert, TransactAsset, WeightBounds},
 		Assets,
 	};
 
@@ -153,6 +321,274 @@ pub mod pallet {
 		fn whitelist_token(_b: u32) -> Weight;
 		fn remove_whitelisted_token(_b: u32) -> Weight;
 		fn transfer_fee(b: u32) -> Weight;
+		fn transfer_fee_from_pot() -> Weight;
+		fn retry_failed_withdrawal() -> Weight;
+		fn refund_failed_withdrawal() -> Weight;
+		fn discard_failed_withdrawal() -> Weight;
+		fn execute_withdrawal() -> Weight;
+		fn set_asset_withdrawal_config() -> Weight;
+		fn set_asset_rate_limit() -> Weight;
+		fn pause_bridge() -> Weight;
+		fn unpause_bridge() -> Weight;
+		fn freeze_asset() -> Weight;
+		fn unfreeze_asset() -> Weight;
+		fn set_asset_metadata() -> Weight;
+		fn add_asset_alias() -> Weight;
+		fn remove_asset_alias() -> Weight;
+		fn set_canonical_asset_location() -> Weight;
+		fn set_abstract_asset_location() -> Weight;
+		fn set_asset_non_fungible() -> Weight;
+		fn set_asset_price() -> Weight;
+		fn set_asset_fee_rate() -> Weight;
+		fn refund_unmigrated_withdrawal() -> Weight;
+		fn refund_rejected_withdrawal() -> Weight;
+	}
+
+	/// Reason why withdrawal could not be executed
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum WithdrawalFailureReason {
+		/// Withdrawal is blocked by Thea Council
+		BlockedByCouncil,
+		/// Not able to decode destination
+		DestinationDecodeFailed,
+		/// Asset id is not mapped to any foreign asset
+		UnknownAssetMapping,
+		/// Not able to mint token
+		MintFailed(DispatchError),
+		/// Not able to make xcm calls
+		XcmTransferFailed(DispatchError),
+		/// Not able to deposit to local account
+		LocalDepositFailed(DispatchError),
+		/// Storage transaction could not be opened or committed
+		TransactionFailed(DispatchError),
+		/// Reason was not recorded, withdrawal failed before reasons were stored
+		Unknown,
+		/// Amount is above the rate limit of the asset, so it can never be executed
+		ExceedsRateLimit,
+		/// Withdrawal of non-fungible collection doesn't identify an instance deposited over XCM
+		InvalidNonFungibleInstance,
+		/// Non-fungible asset can only be withdrawn to an account on its reserve chain
+		UnsupportedNonFungibleDestination,
+		/// XCM message sending the asset failed to execute, carries error of XCM executor
+		XcmExecutionFailed(XcmError),
+		/// Instance of non-fungible asset can only be withdrawn with an amount of one
+		InvalidNonFungibleAmount,
+	}
+
+	/// Reason why withdrawal execution is postponed
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum WithdrawalDeferralReason {
+		/// Withdrawn amount of asset reached the rate limit of current window
+		RateLimited,
+		/// Asset is frozen
+		AssetFrozen,
+	}
+
+	/// Route taken by executed withdrawal
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum WithdrawalRoute {
+		/// Transferred to foreign chain using XCM
+		Xcm,
+		/// Deposited to account on native Parachain
+		Local,
+	}
+
+	/// Reason of `XcmError::Trap` returned while depositing asset, assets of the message are
+	/// trapped and can be claimed once the cause is resolved. Error code is the discriminant of
+	/// the reason, which is also emitted in `DepositTrapped` event.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TrapCode {
+		/// Thea rejected the deposit
+		TheaDepositFailed = 102,
+		/// Inbound deposits are paused
+		InboundPaused = 103,
+		/// Bridging of asset is frozen
+		AssetFrozen = 104,
+	}
+
+	impl From<TrapCode> for XcmError {
+		fn from(code: TrapCode) -> Self {
+			XcmError::Trap(code as u64)
+		}
+	}
+
+	impl TryFrom<u64> for TrapCode {
+		type Error = ();
+
+		/// Decodes reason from code of `XcmError::Trap`
+		fn try_from(code: u64) -> Result<Self, Self::Error> {
+			match code {
+				102 => Ok(Self::TheaDepositFailed),
+				103 => Ok(Self::InboundPaused),
+				104 => Ok(Self::AssetFrozen),
+				_ => Err(()),
+			}
+		}
+	}
+
+	/// Extra data of deposit sent to Thea, used to correlate the deposit with XCM message which
+	/// delivered the asset
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct DepositExtra {
+		/// Id of XCM message
+		pub message_id: XcmHash,
+		/// Origin of XCM message, `None` if the message cleared it before depositing the asset
+		pub origin: Option<MultiLocation>,
+		/// Instance of non-fungible asset
+		pub instance: Option<AssetInstance>,
+	}
+
+	/// Maximum length of Thea withdrawal id
+	pub type WithdrawalIdLimit = ConstU32<64>;
+	/// Maximum length of encoded withdrawal destination
+	pub type WithdrawalDestinationLimit = ConstU32<256>;
+	/// Maximum length of withdrawal extra data
+	pub type WithdrawalExtraLimit = ConstU32<256>;
+	/// Thea withdrawal id
+	pub type WithdrawalId = BoundedVec<u8, WithdrawalIdLimit>;
+	/// Maximum length of asset name and symbol
+	pub type AssetMetadataLimit = ConstU32<50>;
+
+	/// Withdrawal received from Thea with bounded fields, encoded the same way as [`Withdraw`]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct BoundedWithdraw {
+		/// Thea withdrawal id
+		pub id: WithdrawalId,
+		/// Asset id
+		pub asset_id: u128,
+		/// Amount to withdraw
+		pub amount: u128,
+		/// Encoded destination
+		pub destination: BoundedVec<u8, WithdrawalDestinationLimit>,
+		/// Blocked by Thea Council
+		pub is_blocked: bool,
+		/// Extra data
+		pub extra: BoundedVec<u8, WithdrawalExtraLimit>,
+	}
+
+	impl TryFrom<Withdraw> for BoundedWithdraw {
+		type Error = Withdraw;
+
+		/// Returns original withdrawal back if any of its fields exceeds the limits
+		fn try_from(withdraw: Withdraw) -> Result<Self, Self::Error> {
+			if withdraw.id.len() > WithdrawalIdLimit::get() as usize ||
+				withdraw.destination.len() > WithdrawalDestinationLimit::get() as usize ||
+				withdraw.extra.len() > WithdrawalExtraLimit::get() as usize
+			{
+				return Err(withdraw)
+			}
+			Ok(Self {
+				id: BoundedVec::truncate_from(withdraw.id),
+				asset_id: withdraw.asset_id,
+				amount: withdraw.amount,
+				destination: BoundedVec::truncate_from(withdraw.destination),
+				is_blocked: withdraw.is_blocked,
+				extra: BoundedVec::truncate_from(withdraw.extra),
+			})
+		}
+	}
+
+	impl From<BoundedWithdraw> for Withdraw {
+		fn from(withdraw: BoundedWithdraw) -> Self {
+			Self {
+				id: withdraw.id.into_inner(),
+				asset_id: withdraw.asset_id,
+				amount: withdraw.amount,
+				destination: withdraw.destination.into_inner(),
+				is_blocked: withdraw.is_blocked,
+				extra: withdraw.extra.into_inner(),
+			}
+		}
+	}
+
+	/// Withdrawal which failed during execution
+	#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct FailedWithdrawal<BlockNumber> {
+		/// Withdrawal received from Thea
+		pub withdraw: BoundedWithdraw,
+		/// Reason of failure
+		pub reason: WithdrawalFailureReason,
+		/// Block in which execution was attempted
+		pub attempted_at: BlockNumber,
+	}
+
+	/// Withdrawal delays configured for an asset
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AssetWithdrawalConfig<BlockNumber> {
+		/// Delay in blocks applied to withdrawals of the asset
+		pub delay: BlockNumber,
+		/// Withdrawals with amount above the threshold are treated as large withdrawals
+		pub large_withdrawal_threshold: Option<u128>,
+		/// Delay in blocks applied to large withdrawals, if not set large withdrawals are held
+		/// until released by Thea Council
+		pub large_withdrawal_delay: Option<BlockNumber>,
+	}
+
+	/// Direction of assets crossing the bridge
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum BridgeDirection {
+		/// Deposits received from foreign chains over XCM
+		Inbound,
+		/// Withdrawals received from Thea and executed in `on_initialize`
+		Outbound,
+	}
+
+	/// Maximum amount of asset which can be withdrawn within a window
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AssetRateLimit<BlockNumber> {
+		/// Length of window in blocks
+		pub window: BlockNumber,
+		/// Maximum amount withdrawn within a window
+		pub max_amount: u128,
+	}
+
+	/// Amount of asset withdrawn in the current window
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct WithdrawalUsage<BlockNumber> {
+		/// Block in which the window started
+		pub window_start: BlockNumber,
+		/// Amount withdrawn since window start
+		pub withdrawn: u128,
+	}
+
+	/// Registry entry of an asset bridged over XCM
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AssetMetadata {
+		/// Name of asset
+		pub name: BoundedVec<u8, AssetMetadataLimit>,
+		/// Symbol of asset
+		pub symbol: BoundedVec<u8, AssetMetadataLimit>,
+		/// Number of decimals used to represent amounts
+		pub decimals: u8,
+		/// Minimum balance of asset, applied when the asset is created
+		pub min_balance: u128,
+		/// Canonical location of asset
+		pub location: MultiLocation,
+	}
+
+	/// Fixed fee rate of XCM execution paid in an asset
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AssetFeeRate {
+		/// Amount of asset charged per second of execution time
+		pub units_per_second: u128,
+		/// Amount of asset charged per byte of proof size
+		pub units_per_pov_byte: u128,
+	}
+
+	impl AssetFeeRate {
+		/// Returns amount of asset charged for given weight
+		pub fn fee(&self, weight: Weight) -> u128 {
+			let execution_fee = self.units_per_second.saturating_mul(weight.ref_time().into()) /
+				u128::from(WEIGHT_REF_TIME_PER_SECOND);
+			let proof_fee = self.units_per_pov_byte.saturating_mul(weight.proof_size().into());
+			execution_fee.saturating_add(proof_fee)
+		}
+	}
+
+	impl From<DispatchError> for WithdrawalFailureReason {
+		fn from(error: DispatchError) -> Self {
+			Self::TransactionFailed(error)
+		}
 	}
 
 	pub trait AssetIdConverter {
@@ -167,6 +603,60 @@ pub mod pallet {
 		fn check_whitelisted_token(asset_id: u128) -> bool;
 	}
 
+	pub trait AssetFeeRateProvider {
+		/// Returns fixed fee rate of XCM execution paid in asset
+		fn get_fee_rate(asset_id: u128) -> Option<AssetFeeRate>;
+	}
+
+	pub trait AssetPriceProvider {
+		/// Returns amount of asset equivalent to one unit of native token
+		fn get_native_token_price(asset_id: u128) -> Option<FixedU128>;
+	}
+
+	/// Swaps assets using AMM pools
+	pub trait Swap<AccountId> {
+		/// Returns amount of `asset_in` needed to receive `amount_out` of `asset_out`, `None` if
+		/// there is no pool for the pair
+		fn quote_price_tokens_for_exact_tokens(
+			asset_in: u128,
+			asset_out: u128,
+			amount_out: u128,
+		) -> Option<u128>;
+		/// Swaps at most `amount_in_max` of `asset_in` owned by `who` for exactly `amount_out` of
+		/// `asset_out`, returns amount of `asset_in` spent
+		fn swap_tokens_for_exact_tokens(
+			who: &AccountId,
+			asset_in: u128,
+			asset_out: u128,
+			amount_out: u128,
+			amount_in_max: u128,
+		) -> Result<u128, DispatchError>;
+	}
+
+	impl<AccountId> Swap<AccountId> for () {
+		fn quote_price_tokens_for_exact_tokens(_: u128, _: u128, _: u128) -> Option<u128> {
+			None
+		}
+
+		fn swap_tokens_for_exact_tokens(
+			_: &AccountId,
+			_: u128,
+			_: u128,
+			_: u128,
+			_: u128,
+		) -> Result<u128, DispatchError> {
+			Err(DispatchError::Other("No pool"))
+		}
+	}
+
+	pub trait NativeFeeSwapper {
+		/// Returns amount of asset needed to pay `fee` in native token, `None` if asset can't be
+		/// swapped into native token
+		fn quote_native_fee(asset_id: u128, fee: u128) -> Option<u128>;
+		/// Swaps `amount` of asset taken as XCM fee into `fee` in native token
+		fn swap_into_native_fee(asset_id: u128, amount: u128, fee: u128) -> DispatchResult;
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + orml_xtokens::Config {
@@ -177,7 +667,8 @@ pub mod pallet {
 		/// Assets
 		type Assets: frame_support::traits::tokens::fungibles::Mutate<Self::AccountId>
 			+ frame_support::traits::tokens::fungibles::Create<Self::AccountId>
-			+ frame_support::traits::tokens::fungibles::Inspect<Self::AccountId>;
+			+ frame_support::traits::tokens::fungibles::Inspect<Self::AccountId>
+			+ frame_support::traits::tokens::fungibles::metadata::Mutate<Self::AccountId>;
 		/// Asset Id
 		type AssetId: Member
 			+ Parameter
@@ -197,17 +688,30 @@ pub mod pallet {
 		/// Pallet Id
 		#[pallet::constant]
 		type AssetHandlerPalletId: Get<PalletId>;
-		/// Pallet Id
+		/// Default delay in blocks after which withdrawal is executed
 		#[pallet::constant]
 		type WithdrawalExecutionBlockDiff: Get<Self::BlockNumber>;
-		/// PDEX Asset ID
-		#[pallet::constant]
-		type ParachainId: Get<u32>;
 		#[pallet::constant]
 		type SubstrateNetworkId: Get<u8>;
 		/// Native Asset Id
 		#[pallet::constant]
 		type NativeAssetId: Get<Self::AssetId>;
+		/// Maximum weight spent on executing withdrawals in a single block
+		#[pallet::constant]
+		type WithdrawalExecutionWeightLimit: Get<Weight>;
+		/// Maximum number of withdrawals scheduled for execution in a single block
+		#[pallet::constant]
+		type MaxPendingWithdrawalsPerBlock: Get<u32>;
+		/// Maximum number of withdrawals which can fail in a single block
+		#[pallet::constant]
+		type MaxFailedWithdrawalsPerBlock: Get<u32>;
+		/// Maximum number of whitelisted tokens
+		#[pallet::constant]
+		type MaxWhitelistedTokens: Get<u32>;
+		/// AMM used to swap XCM fees paid in assets into native token
+		type Swap: Swap<Self::AccountId>;
+		/// Account collecting XCM fees paid in foreign assets
+		type FeePotAccount: Get<Self::AccountId>;
 		/// Weight Info
 		type WeightInfo: XcmHelperWeightInfo;
 	}
@@ -215,32 +719,169 @@ pub mod pallet {
 	/// Pending Withdrawals
 	#[pallet::storage]
 	#[pallet::getter(fn get_pending_withdrawals)]
-	pub(super) type PendingWithdrawals<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<Withdraw>, ValueQuery>;
+	pub(super) type PendingWithdrawals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		BoundedVec<BoundedWithdraw, T::MaxPendingWithdrawalsPerBlock>,
+		ValueQuery,
+	>;
 
 	/// Failed Withdrawals
 	#[pallet::storage]
 	#[pallet::getter(fn get_failed_withdrawals)]
-	pub(super) type FailedWithdrawals<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<Withdraw>, ValueQuery>;
+	pub(super) type FailedWithdrawals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		BoundedVec<FailedWithdrawal<T::BlockNumber>, T::MaxFailedWithdrawalsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Block in which failed withdrawal is stored, keyed by Thea withdrawal id
+	#[pallet::storage]
+	#[pallet::getter(fn get_failed_withdrawal_block)]
+	pub(super) type FailedWithdrawalIndex<T: Config> =
+		StorageMap<_, Blake2_128Concat, WithdrawalId, T::BlockNumber, OptionQuery>;
+
+	/// Withdrawals exceeding the size limits of bounded storage, kept by migration to v2 until
+	/// they are refunded. Keyed by hash of Thea withdrawal id as the id itself may be oversized.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn get_unmigrated_withdrawal)]
+	pub type UnmigratedWithdrawals<T: Config> =
+		StorageMap<_, Identity, [u8; 32], Withdraw, OptionQuery>;
+
+	/// Whitelisted tokens exceeding `MaxWhitelistedTokens`, kept by migration to v2 so they can
+	/// be whitelisted again once there is room
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn get_unmigrated_whitelisted_tokens)]
+	pub type UnmigratedWhitelistedTokens<T: Config> = StorageValue<_, Vec<u128>, ValueQuery>;
+
+	/// Rejected withdrawals which couldn't be refunded back to Thea automatically, kept until
+	/// they are refunded by governance. Keyed by hash of Thea withdrawal id as the id itself may
+	/// be oversized.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn get_rejected_withdrawals)]
+	pub type RejectedWithdrawals<T: Config> =
+		StorageMap<_, Identity, [u8; 32], Vec<Withdraw>, ValueQuery>;
 
 	/// Asset mapping from u128 asset to multi asset.
 	#[pallet::storage]
 	#[pallet::getter(fn assets_mapping)]
 	pub type ParachainAssets<T: Config> = StorageMap<_, Identity, u128, AssetId, OptionQuery>;
 
+	/// Asset mapping from canonical multi asset to u128 asset.
+	#[pallet::storage]
+	#[pallet::getter(fn get_parachain_asset_id)]
+	pub type ParachainAssetIds<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetId, u128, OptionQuery>;
+
+	/// Locations used for withdrawals of abstract assets
+	#[pallet::storage]
+	#[pallet::getter(fn get_abstract_asset_location)]
+	pub type AbstractAssetLocations<T: Config> =
+		StorageMap<_, Identity, u128, MultiLocation, OptionQuery>;
+
 	/// Whitelist Tokens
 	#[pallet::storage]
 	#[pallet::getter(fn get_whitelisted_tokens)]
-	pub type WhitelistedTokens<T: Config> = StorageValue<_, Vec<u128>, ValueQuery>;
+	pub type WhitelistedTokens<T: Config> =
+		StorageValue<_, BoundedVec<u128, T::MaxWhitelistedTokens>, ValueQuery>;
 
 	/// Nonce used to generate randomness for txn id
 	#[pallet::storage]
 	#[pallet::getter(fn randomness_nonce)]
 	pub type RandomnessNonce<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	/// Block in which pending withdrawal is scheduled for execution, keyed by Thea withdrawal id
+	#[pallet::storage]
+	#[pallet::getter(fn get_withdrawal_block)]
+	pub(super) type WithdrawalIndex<T: Config> =
+		StorageMap<_, Blake2_128Concat, WithdrawalId, T::BlockNumber, OptionQuery>;
+
+	/// Withdrawal delays configured per asset
+	#[pallet::storage]
+	#[pallet::getter(fn get_asset_withdrawal_config)]
+	pub type AssetWithdrawalConfigs<T: Config> =
+		StorageMap<_, Identity, u128, AssetWithdrawalConfig<T::BlockNumber>, OptionQuery>;
+
+	/// Large withdrawals waiting for release by Thea Council, keyed by Thea withdrawal id
+	#[pallet::storage]
+	#[pallet::getter(fn get_held_withdrawal)]
+	pub(super) type HeldWithdrawals<T: Config> =
+		StorageMap<_, Blake2_128Concat, WithdrawalId, BoundedWithdraw, OptionQuery>;
+
+	/// Withdrawal rate limits configured per asset
+	#[pallet::storage]
+	#[pallet::getter(fn get_asset_rate_limit)]
+	pub type AssetRateLimits<T: Config> =
+		StorageMap<_, Identity, u128, AssetRateLimit<T::BlockNumber>, OptionQuery>;
+
+	/// Amount withdrawn per rate limited asset in the current window
+	#[pallet::storage]
+	#[pallet::getter(fn get_withdrawal_usage)]
+	pub(super) type WithdrawalUsages<T: Config> =
+		StorageMap<_, Identity, u128, WithdrawalUsage<T::BlockNumber>, OptionQuery>;
+
+	/// Inbound deposits are paused
+	#[pallet::storage]
+	#[pallet::getter(fn is_inbound_paused)]
+	pub type InboundPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Outbound withdrawal execution is paused
+	#[pallet::storage]
+	#[pallet::getter(fn is_outbound_paused)]
+	pub type OutboundPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// First block whose pending withdrawals aren't executed yet, set while outbound withdrawals
+	/// are paused and removed once the backlog is drained
+	#[pallet::storage]
+	#[pallet::getter(fn get_withdrawal_backlog_start)]
+	pub type WithdrawalBacklogStart<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// Assets whose deposits are trapped and withdrawals are deferred
+	#[pallet::storage]
+	#[pallet::getter(fn get_frozen_asset)]
+	pub type FrozenAssets<T: Config> = StorageMap<_, Identity, u128, (), OptionQuery>;
+
+	/// Metadata and canonical location registered per asset
+	#[pallet::storage]
+	#[pallet::getter(fn get_asset_metadata)]
+	pub type AssetRegistry<T: Config> = StorageMap<_, Identity, u128, AssetMetadata, OptionQuery>;
+
+	/// Assets whose instances are bridged as non-fungible tokens
+	#[pallet::storage]
+	#[pallet::getter(fn get_non_fungible_asset)]
+	pub type NonFungibleAssets<T: Config> = StorageMap<_, Identity, u128, (), OptionQuery>;
+
+	/// Collection and instance of non-fungible assets deposited over XCM, keyed by asset id of
+	/// the instance
+	#[pallet::storage]
+	#[pallet::getter(fn get_non_fungible_instance)]
+	pub type NonFungibleInstances<T: Config> =
+		StorageMap<_, Identity, u128, (u128, AssetInstance), OptionQuery>;
+
+	/// Price of native token in asset, used to charge XCM execution fees in foreign assets
+	#[pallet::storage]
+	#[pallet::getter(fn get_asset_price)]
+	pub type AssetPrices<T: Config> = StorageMap<_, Identity, u128, FixedU128, OptionQuery>;
+
+	/// Fixed fee rates of XCM execution paid in foreign assets
+	#[pallet::storage]
+	#[pallet::getter(fn get_asset_fee_rate)]
+	pub type AssetFeeRates<T: Config> = StorageMap<_, Identity, u128, AssetFeeRate, OptionQuery>;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
+	/// Maximum number of blocks of withdrawals read by a single page of withdrawal queries
+	pub const MAX_WITHDRAWAL_QUERY_BLOCKS: u32 = 100;
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// Pallets use events to inform users when important changes are made.
@@ -249,19 +890,178 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Asset Deposited from XCM
-		/// parameters. [recipient, multiasset, asset_id]
-		AssetDeposited(Box<MultiLocation>, Box<MultiAsset>, u128),
+		/// parameters. [recipient, multiasset, asset_id, message_id, origin]
+		AssetDeposited(
+			Box<MultiLocation>,
+			Box<MultiAsset>,
+			u128,
+			XcmHash,
+			Option<Box<MultiLocation>>,
+		),
 		AssetWithdrawn(T::AccountId, Box<MultiAsset>),
 		/// New Asset Created [asset_id]
 		TheaAssetCreated(u128),
 		/// Token Whitelisted For Xcm [token]
 		TokenWhitelistedForXcm(u128),
-		/// Xcm Fee Transferred [recipient, amount]
-		XcmFeeTransferred(T::AccountId, u128),
+		/// Xcm Fee Transferred [recipient, asset_id, amount]
+		XcmFeeTransferred(T::AccountId, u128, u128),
 		/// Native asset id mapping is registered
 		NativeAssetIdMappingRegistered(u128, Box<AssetId>),
 		/// Whitelisted Token removed
 		WhitelistedTokenRemoved(u128),
+		/// Failed withdrawal queued for execution again
+		FailedWithdrawalRequeued {
+			id: Vec<u8>,
+			execute_at: T::BlockNumber,
+		},
+		/// Failed withdrawal refunded back to Thea
+		FailedWithdrawalRefunded {
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		},
+		/// Failed withdrawal removed permanently
+		FailedWithdrawalDiscarded {
+			id: Vec<u8>,
+		},
+		/// Withdrawal queued for execution
+		WithdrawalQueued {
+			id: Vec<u8>,
+			execute_at: T::BlockNumber,
+		},
+		/// Withdrawal executed successfully
+		WithdrawalExecuted {
+			id: Vec<u8>,
+			asset_id: u128,
+			amount: u128,
+			destination: Box<VersionedMultiLocation>,
+			route: WithdrawalRoute,
+		},
+		/// Withdrawal execution failed
+		WithdrawalFailed {
+			id: Vec<u8>,
+			reason: WithdrawalFailureReason,
+		},
+		/// Withdrawal delays of asset are updated
+		AssetWithdrawalConfigUpdated {
+			asset_id: u128,
+			config: Option<AssetWithdrawalConfig<T::BlockNumber>>,
+		},
+		/// Large withdrawal is held until released by Thea Council
+		WithdrawalHeld {
+			id: Vec<u8>,
+		},
+		/// Held withdrawal released and queued for execution
+		HeldWithdrawalReleased {
+			id: Vec<u8>,
+			execute_at: T::BlockNumber,
+		},
+		/// Withdrawal rate limit of asset is updated
+		AssetRateLimitUpdated {
+			asset_id: u128,
+			limit: Option<AssetRateLimit<T::BlockNumber>>,
+		},
+		/// Withdrawal execution is postponed
+		WithdrawalDeferred {
+			id: Vec<u8>,
+			execute_at: T::BlockNumber,
+			reason: WithdrawalDeferralReason,
+		},
+		/// Bridge paused in given direction
+		BridgePaused {
+			direction: BridgeDirection,
+		},
+		/// Bridge unpaused in given direction
+		BridgeUnpaused {
+			direction: BridgeDirection,
+		},
+		/// Bridging of asset is frozen
+		AssetFrozen {
+			asset_id: u128,
+		},
+		/// Bridging of asset is unfrozen
+		AssetUnfrozen {
+			asset_id: u128,
+		},
+		/// Metadata of asset is registered or updated
+		AssetMetadataUpdated {
+			asset_id: u128,
+			metadata: AssetMetadata,
+		},
+		/// Alias location of asset is registered
+		AssetAliasAdded {
+			asset_id: u128,
+			alias: AssetId,
+		},
+		/// Alias location of asset is removed
+		AssetAliasRemoved {
+			asset_id: u128,
+			alias: AssetId,
+		},
+		/// Location used for withdrawals of asset is updated
+		CanonicalAssetLocationUpdated {
+			asset_id: u128,
+			location: AssetId,
+		},
+		/// Location used for withdrawals of abstract asset is updated
+		AbstractAssetLocationUpdated {
+			asset_id: u128,
+			location: Option<MultiLocation>,
+		},
+		/// Asset is marked as fungible or non-fungible
+		AssetFungibilityUpdated {
+			asset_id: u128,
+			non_fungible: bool,
+		},
+		/// Price of native token in asset is updated
+		AssetPriceUpdated {
+			asset_id: u128,
+			price: Option<FixedU128>,
+		},
+		/// Fixed fee rate of XCM execution paid in asset is updated
+		AssetFeeRateUpdated {
+			asset_id: u128,
+			rate: Option<AssetFeeRate>,
+		},
+		/// Deposit is rejected and its assets are trapped
+		DepositTrapped {
+			beneficiary: Box<MultiLocation>,
+			asset: Box<MultiAsset>,
+			reason: TrapCode,
+		},
+		/// Batch of withdrawals received from Thea is rejected as it can't be stored
+		WithdrawalsRejected {
+			ids: Vec<Vec<u8>>,
+			reason: DispatchError,
+		},
+		/// Rejected withdrawals are refunded back to Thea
+		RejectedWithdrawalsRefunded {
+			ids: Vec<Vec<u8>>,
+		},
+		/// Rejected withdrawals which couldn't be refunded are kept for refund by governance
+		RejectedWithdrawalsKept {
+			ids: Vec<Vec<u8>>,
+		},
+		/// XCM fee is deposited into the fee pot
+		XcmFeeCollected {
+			asset_id: u128,
+			amount: u128,
+		},
+		/// XCM fee paid in asset is swapped into native token
+		XcmFeeSwapped {
+			asset_id: u128,
+			amount_in: u128,
+			fee: u128,
+		},
+		/// Withdrawal which couldn't be migrated to bounded storage is refunded back to Thea
+		UnmigratedWithdrawalRefunded {
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		},
+		/// Kept rejected withdrawal is refunded back to Thea
+		RejectedWithdrawalRefunded {
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -273,8 +1073,6 @@ pub mod pallet {
 		IndexNotFound,
 		/// Identifier Length Mismatch
 		IdentifierLengthMismatch,
-		/// AssetId Abstract Not Handled
-		AssetIdAbstractNotHandled,
 		/// Pending withdrawal Limit Reached
 		PendingWithdrawalsLimitReached,
 		/// Token is already Whitelisted
@@ -297,82 +1095,189 @@ pub mod pallet {
 		WithdrawalExecutionFailed,
 		/// Token Is Not Whitelisted
 		TokenIsNotWhitelisted,
+		/// Withdrawal is not pending, it is already executed or unknown
+		WithdrawalNotPending,
+		/// Withdrawal fields exceed the storage limits
+		WithdrawalTooLarge,
+		/// Failed withdrawals limit reached
+		FailedWithdrawalsLimitReached,
+		/// Withdrawal is not held
+		WithdrawalNotHeld,
+		/// Withdrawal is not failed, it is already handled or unknown
+		WithdrawalNotFailed,
+		/// Min balance of already created asset can't be changed
+		AssetMinBalanceMismatch,
+		/// Asset is not registered
+		AssetNotRegistered,
+		/// Location is already mapped to an asset
+		AssetLocationAlreadyMapped,
+		/// Location is not mapped to given asset
+		AssetLocationNotMapped,
+		/// Canonical location of asset can't be removed
+		CanonicalAssetLocation,
+		/// Asset is not identified by an abstract id
+		AssetIsNotAbstract,
+		/// Withdrawal delay has to be at least one block
+		InvalidWithdrawalDelay,
+		/// Withdrawal is not kept by migration or it is already refunded
+		UnmigratedWithdrawalNotFound,
+		/// Rejected withdrawal is not kept or it is already refunded
+		RejectedWithdrawalNotFound,
+		/// Withdrawal with the same id is already pending, held or failed
+		DuplicateWithdrawalId,
+		/// Asset is not frozen
+		AssetNotFrozen,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_initialize(n: T::BlockNumber) -> Weight {
-			let mut failed_withdrawal: Vec<Withdraw> = Vec::default();
-			<PendingWithdrawals<T>>::mutate(n, |withdrawals| {
-				while let Some(withdrawal) = withdrawals.pop() {
-					if !withdrawal.is_blocked {
-						let destination = match VersionedMultiLocation::decode(
-							&mut &withdrawal.destination[..],
-						) {
-							Ok(dest) => dest,
-							Err(_) => {
-								failed_withdrawal.push(withdrawal);
-								log::error!(target:"xcm-helper","Withdrawal failed: Not able to decode destination");
-								continue
-							},
+			let backlog_start = <WithdrawalBacklogStart<T>>::get();
+			// While paused, withdrawals are left in place and drained starting from the first
+			// skipped block once outbound withdrawals are unpaused
+			if <OutboundPaused<T>>::get() {
+				if backlog_start.is_none() {
+					<WithdrawalBacklogStart<T>>::put(n);
+					return T::DbWeight::get().reads_writes(2, 1)
+				}
+				return T::DbWeight::get().reads(2)
+			}
+			let mut block_no = backlog_start.unwrap_or(n);
+			let mut withdrawals = <PendingWithdrawals<T>>::take(block_no).into_inner();
+			if backlog_start.is_none() && withdrawals.is_empty() {
+				return T::DbWeight::get().reads(3)
+			}
+			let budget = T::WithdrawalExecutionWeightLimit::get();
+			let weight_per_withdrawal = T::WeightInfo::execute_withdrawal();
+			let mut consumed_weight = T::DbWeight::get().reads_writes(4, 1);
+			let mut failed_withdrawals = <FailedWithdrawals<T>>::get(n);
+			let failed_withdrawals_before = failed_withdrawals.len();
+			let mut deferred_withdrawals: BTreeMap<T::BlockNumber, Vec<BoundedWithdraw>> =
+				BTreeMap::new();
+			loop {
+				while !withdrawals.is_empty() {
+					let next_weight = consumed_weight.saturating_add(weight_per_withdrawal);
+					// Stop if there is no weight left or no room to record another failure
+					if next_weight.any_gt(budget) ||
+						failed_withdrawals.len() >=
+							T::MaxFailedWithdrawalsPerBlock::get() as usize
+					{
+						break
+					}
+					consumed_weight = next_weight;
+					if let Some(withdrawal) = withdrawals.pop() {
+						<WithdrawalIndex<T>>::remove(&withdrawal.id);
+						let deferral = if Self::is_asset_frozen(withdrawal.asset_id) {
+							// Frozen asset is checked again after the default delay
+							Some((
+								n.saturating_add(T::WithdrawalExecutionBlockDiff::get()),
+								WithdrawalDeferralReason::AssetFrozen,
+							))
+						} else {
+							Self::get_rate_limit_window_end(
+								n,
+								withdrawal.asset_id,
+								withdrawal.amount,
+							)
+							.map(|execute_at| (execute_at, WithdrawalDeferralReason::RateLimited))
 						};
-						if !Self::is_polkadex_parachain_destination(&destination) {
-							if let Some(asset) = Self::assets_mapping(withdrawal.asset_id) {
-								let multi_asset = MultiAsset {
-									id: asset,
-									fun: Fungibility::Fungible(withdrawal.amount),
-								};
-								let pallet_account: T::AccountId =
-									T::AssetHandlerPalletId::get().into_account_truncating();
-								// Mint
-								if Self::resolver_deposit(
-									withdrawal.asset_id.into(),
-									withdrawal.amount,
-									&pallet_account,
-									pallet_account.clone(),
-									1u128,
-									pallet_account.clone(),
-								)
-								.is_err()
-								{
-									failed_withdrawal.push(withdrawal.clone());
-									log::error!(target:"xcm-helper","Withdrawal failed: Not able to mint token");
-								};
-								if orml_xtokens::module::Pallet::<T>::transfer_multiassets(
-									RawOrigin::Signed(
-										T::AssetHandlerPalletId::get().into_account_truncating(),
-									)
-									.into(),
-									Box::new(multi_asset.into()),
-									0,
-									Box::new(destination.clone()),
-									cumulus_primitives_core::WeightLimit::Unlimited,
-								)
-								.is_err()
-								{
-									failed_withdrawal.push(withdrawal.clone());
-									log::error!(target:"xcm-helper","Withdrawal failed: Not able to make xcm calls");
-								}
-							} else {
-								failed_withdrawal.push(withdrawal)
-							}
-						} else if Self::handle_deposit(withdrawal.clone(), destination).is_err() {
-							failed_withdrawal.push(withdrawal);
-							log::error!(target:"xcm-helper","Withdrawal failed: Not able to handle dest");
+						if let Some((execute_at, reason)) = deferral {
+							Self::deposit_event(Event::<T>::WithdrawalDeferred {
+								id: withdrawal.id.to_vec(),
+								execute_at,
+								reason,
+							});
+							// Withdrawals executed first are kept at the end
+							deferred_withdrawals
+								.entry(execute_at)
+								.or_default()
+								.insert(0, withdrawal);
+							continue
+						}
+						if let Err(reason) = Self::execute_withdrawal(&withdrawal) {
+							log::error!(target:"xcm-helper","Withdrawal failed: {:?}", reason);
+							Self::deposit_event(Event::<T>::WithdrawalFailed {
+								id: withdrawal.id.to_vec(),
+								reason,
+							});
+							<FailedWithdrawalIndex<T>>::insert(&withdrawal.id, n);
+							consumed_weight =
+								consumed_weight.saturating_add(T::DbWeight::get().writes(1));
+							// Can't fail, capacity is checked before execution
+							let _ = failed_withdrawals.try_push(FailedWithdrawal {
+								withdraw: withdrawal,
+								reason,
+								attempted_at: n,
+							});
 						}
-					} else {
-						failed_withdrawal.push(withdrawal);
-						log::error!(target:"xcm-helper","Withdrawal failed: Withdrawal is blocked");
 					}
 				}
-			});
-			// Only update the storage if vector is not empty
-			if !failed_withdrawal.is_empty() {
-				<FailedWithdrawals<T>>::insert(n, failed_withdrawal);
+				// Stop if the block isn't fully executed or the backlog is drained
+				if !withdrawals.is_empty() || block_no >= n {
+					break
+				}
+				let next_weight =
+					consumed_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				if next_weight.any_gt(budget) {
+					break
+				}
+				consumed_weight = next_weight;
+				block_no = block_no.saturating_add(One::one());
+				withdrawals = <PendingWithdrawals<T>>::take(block_no).into_inner();
+			}
+			if block_no < n {
+				// Backlog isn't drained yet, rest of it stays in place and is executed first in
+				// the next block
+				if !withdrawals.is_empty() {
+					<PendingWithdrawals<T>>::insert(
+						block_no,
+						BoundedVec::truncate_from(withdrawals),
+					);
+				}
+				<WithdrawalBacklogStart<T>>::put(block_no);
+				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().writes(2));
+			} else {
+				if backlog_start.is_some() {
+					<WithdrawalBacklogStart<T>>::kill();
+					consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().writes(1));
+				}
+				// Carry over withdrawals which didn't fit in this block, they are executed
+				// first in the next block.
+				if !withdrawals.is_empty() {
+					consumed_weight = consumed_weight.saturating_add(Self::schedule_withdrawals(
+						n.saturating_add(One::one()),
+						withdrawals,
+					));
+				}
+			}
+			for (execute_at, withdrawals) in deferred_withdrawals {
+				consumed_weight = consumed_weight
+					.saturating_add(Self::schedule_withdrawals(execute_at, withdrawals));
 			}
-			// TODO: We are currently over estimating the weight here to 1/4th of total block time
-			// 	Need a better way to estimate this hook
-			MAXIMUM_BLOCK_WEIGHT.saturating_div(4)
+			// Only update the storage if new withdrawals failed
+			if failed_withdrawals.len() > failed_withdrawals_before {
+				<FailedWithdrawals<T>>::insert(n, failed_withdrawals);
+				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			consumed_weight
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::MaxPendingWithdrawalsPerBlock::get() > 0,
+				"MaxPendingWithdrawalsPerBlock must be greater than zero"
+			);
+			assert!(
+				T::MaxFailedWithdrawalsPerBlock::get() > 0,
+				"MaxFailedWithdrawalsPerBlock must be greater than zero"
+			);
+			assert!(
+				T::WithdrawalExecutionWeightLimit::get().all_gte(
+					T::WeightInfo::execute_withdrawal()
+						.saturating_add(T::DbWeight::get().reads_writes(4, 1))
+				),
+				"WithdrawalExecutionWeightLimit must allow at least one withdrawal per block"
+			);
 		}
 	}
 
@@ -387,13 +1292,23 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::whitelist_token(1))]
 		pub fn whitelist_token(origin: OriginFor<T>, token: AssetId) -> DispatchResult {
 			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
-			let token = Self::generate_asset_id_for_parachain(token);
+			let asset = token;
+			let token = Self::get_or_generate_asset_id(asset);
 			let mut whitelisted_tokens = <WhitelistedTokens<T>>::get();
 			ensure!(!whitelisted_tokens.contains(&token), Error::<T>::TokenIsAlreadyWhitelisted);
-			let pallet_account: T::AccountId =
-				T::AssetHandlerPalletId::get().into_account_truncating();
-			Self::resolve_create(token.into(), pallet_account, 1u128)?;
-			whitelisted_tokens.push(token);
+			whitelisted_tokens
+				.try_push(token)
+				.map_err(|_| Error::<T>::WhitelistedTokensLimitReached)?;
+			// Asset may already be created while registering its metadata
+			if !T::Assets::asset_exists(<T as Config>::AssetId::from(token).into()) {
+				let pallet_account: T::AccountId =
+					T::AssetHandlerPalletId::get().into_account_truncating();
+				let min_balance = <AssetRegistry<T>>::get(token)
+					.map(|metadata| metadata.min_balance)
+					.unwrap_or(1u128);
+				Self::resolve_create(token.into(), pallet_account, min_balance)?;
+			}
+			Self::register_asset(asset);
 			<WhitelistedTokens<T>>::put(whitelisted_tokens);
 			Self::deposit_event(Event::<T>::TokenWhitelistedForXcm(token));
 			Ok(())
@@ -406,7 +1321,7 @@ pub mod pallet {
 			token_to_be_removed: AssetId,
 		) -> DispatchResult {
 			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
-			let token_to_be_removed = Self::generate_asset_id_for_parachain(token_to_be_removed);
+			let token_to_be_removed = Self::get_or_generate_asset_id(token_to_be_removed);
 			let mut whitelisted_tokens = <WhitelistedTokens<T>>::get();
 			let index = whitelisted_tokens
 				.iter()
@@ -418,6 +1333,15 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Transfers native XCM fees collected in the pallet account.
+		///
+		/// Deprecated, fees are collected in the fee pot and transferred using
+		/// `transfer_fee_from_pot`. Kept so that native fees collected before the fee pot existed
+		/// can still be transferred.
+		///
+		/// # Parameters
+		///
+		/// * `to`: Recipient of the fees.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::transfer_fee(1))]
 		pub fn transfer_fee(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
@@ -426,88 +1350,601 @@ pub mod pallet {
 			let amount =
 				T::Currency::reducible_balance(&from, Preservation::Preserve, Fortitude::Polite);
 			T::Currency::transfer(&from, &to, amount, Preservation::Protect)?;
-			Self::deposit_event(Event::<T>::XcmFeeTransferred(to, amount.saturated_into()));
+			Self::deposit_event(Event::<T>::XcmFeeTransferred(
+				to,
+				T::NativeAssetId::get().into(),
+				amount.saturated_into(),
+			));
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Convert<u128, Option<MultiLocation>> for Pallet<T> {
-		fn convert(asset_id: u128) -> Option<MultiLocation> {
-			Self::convert_asset_id_to_location(asset_id)
+		/// Re-queues failed withdrawal for execution in the next block.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::retry_failed_withdrawal())]
+		pub fn retry_failed_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			Self::do_retry_failed_withdrawal(&id)
 		}
-	}
 
-	impl<T: Config> TransactAsset for Pallet<T> {
-		/// Generate Ingress Message for new Deposit
-		fn deposit_asset(
-			what: &MultiAsset,
-			who: &MultiLocation,
-			_context: &XcmContext,
-		) -> xcm::latest::Result {
-			// Create approved deposit
-			let MultiAsset { id, fun } = what;
-			let recipient =
-				T::AccountIdConvert::convert_ref(who).map_err(|_| XcmError::FailedToDecode)?;
-			let amount: u128 = Self::get_amount(fun).ok_or(XcmError::Trap(101))?;
-			let asset_id = Self::generate_asset_id_for_parachain(*id);
-			let deposit: Deposit<T::AccountId> = Deposit {
-				id: Self::new_random_id(),
-				recipient,
-				asset_id,
-				amount,
-				extra: Vec::new(),
-			};
+		/// Refunds failed withdrawal back to Thea.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		/// * `recipient`: Account which will be credited on Thea side.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::refund_failed_withdrawal())]
+		pub fn refund_failed_withdrawal(
+			origin: OriginFor<T>,
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			Self::do_refund_failed_withdrawal(&id, recipient)
+		}
 
-			let parachain_network_id = T::SubstrateNetworkId::get();
-			T::Executor::execute_withdrawals(parachain_network_id, sp_std::vec![deposit].encode())
-				.map_err(|_| XcmError::Trap(102))?;
-			Self::deposit_event(Event::<T>::AssetDeposited(
-				Box::new(*who),
-				Box::new(what.clone()),
-				asset_id,
-			));
-			Ok(())
+		/// Removes failed withdrawal permanently.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of failed withdrawal.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::discard_failed_withdrawal())]
+		pub fn discard_failed_withdrawal(origin: OriginFor<T>, id: Vec<u8>) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			Self::do_discard_failed_withdrawal(&id)
 		}
 
-		/// Burns/Lock asset from provided account.
-		//TODO: Check for context
-		fn withdraw_asset(
-			what: &MultiAsset,
-			who: &MultiLocation,
-			_context: Option<&XcmContext>,
-		) -> sp_std::result::Result<Assets, XcmError> {
-			let MultiAsset { id: _, fun } = what;
-			let who =
-				T::AccountIdConvert::convert_ref(who).map_err(|_| XcmError::FailedToDecode)?;
-			let amount: u128 = Self::get_amount(fun).ok_or(XcmError::Trap(101))?;
-			let asset_id = Self::generate_asset_id_for_parachain(what.id);
-			let pallet_account: T::AccountId =
-				T::AssetHandlerPalletId::get().into_account_truncating();
-			Self::resolver_withdraw(asset_id.into(), amount.saturated_into(), &who, pallet_account)
-				.map_err(|_| XcmError::Trap(25))?;
-			Ok(what.clone().into())
+		/// Sets withdrawal delays for an asset, `None` restores the default delay. Delays have to
+		/// be at least one block.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `config`: Withdrawal delays of the asset.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_asset_withdrawal_config())]
+		pub fn set_asset_withdrawal_config(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			config: Option<AssetWithdrawalConfig<T::BlockNumber>>,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			// Withdrawals are queued after `on_initialize` of the current block has run
+			if let Some(config) = config {
+				ensure!(
+					!config.delay.is_zero() &&
+						!config.large_withdrawal_delay.map_or(false, |delay| delay.is_zero()),
+					Error::<T>::InvalidWithdrawalDelay
+				);
+			}
+			<AssetWithdrawalConfigs<T>>::set(asset_id, config);
+			Self::deposit_event(Event::<T>::AssetWithdrawalConfigUpdated { asset_id, config });
+			Ok(())
 		}
 
-		/// Transfers Asset from source account to destination account
-		fn transfer_asset(
-			asset: &MultiAsset,
+		/// Sets withdrawal rate limit for an asset, `None` removes the limit.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `limit`: Maximum amount withdrawn within a window.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_asset_rate_limit())]
+		pub fn set_asset_rate_limit(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			limit: Option<AssetRateLimit<T::BlockNumber>>,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			<AssetRateLimits<T>>::set(asset_id, limit);
+			if limit.is_none() {
+				<WithdrawalUsages<T>>::remove(asset_id);
+			}
+			Self::deposit_event(Event::<T>::AssetRateLimitUpdated { asset_id, limit });
+			Ok(())
+		}
+
+		/// Pauses bridge in given direction.
+		///
+		/// # Parameters
+		///
+		/// * `direction`: Direction to pause.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::pause_bridge())]
+		pub fn pause_bridge(origin: OriginFor<T>, direction: BridgeDirection) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			Self::do_pause_bridge(direction);
+			Ok(())
+		}
+
+		/// Unpauses bridge in given direction. Only governance can unpause the bridge, Thea
+		/// Council can only pause it.
+		///
+		/// # Parameters
+		///
+		/// * `direction`: Direction to unpause.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::unpause_bridge())]
+		pub fn unpause_bridge(origin: OriginFor<T>, direction: BridgeDirection) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			match direction {
+				BridgeDirection::Inbound => <InboundPaused<T>>::kill(),
+				BridgeDirection::Outbound => <OutboundPaused<T>>::kill(),
+			}
+			Self::deposit_event(Event::<T>::BridgeUnpaused { direction });
+			Ok(())
+		}
+
+		/// Freezes bridging of an asset. Deposits of frozen asset are trapped and its
+		/// withdrawals are deferred until the asset is unfrozen.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::freeze_asset())]
+		pub fn freeze_asset(origin: OriginFor<T>, asset_id: u128) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<ParachainAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+			<FrozenAssets<T>>::insert(asset_id, ());
+			Self::deposit_event(Event::<T>::AssetFrozen { asset_id });
+			Ok(())
+		}
+
+		/// Unfreezes bridging of an asset.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::unfreeze_asset())]
+		pub fn unfreeze_asset(origin: OriginFor<T>, asset_id: u128) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<FrozenAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotFrozen);
+			<FrozenAssets<T>>::remove(asset_id);
+			Self::deposit_event(Event::<T>::AssetUnfrozen { asset_id });
+			Ok(())
+		}
+
+		/// Registers metadata of asset identified by its canonical location. Asset is created
+		/// with given min balance if it doesn't exist yet, and name, symbol and decimals are
+		/// applied to the underlying asset. Metadata deposit is reserved from pallet account.
+		///
+		/// # Parameters
+		///
+		/// * `metadata`: Asset metadata.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_asset_metadata())]
+		pub fn set_asset_metadata(
+			origin: OriginFor<T>,
+			mut metadata: AssetMetadata,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			if let AssetId::Concrete(location) =
+				Self::canonicalise_asset(AssetId::Concrete(metadata.location))
+			{
+				metadata.location = location;
+			}
+			let asset_id = Self::register_asset(AssetId::Concrete(metadata.location));
+			let asset: <T::Assets as Inspect<T::AccountId>>::AssetId =
+				<T as Config>::AssetId::from(asset_id).into();
+			let pallet_account: T::AccountId =
+				T::AssetHandlerPalletId::get().into_account_truncating();
+			if T::Assets::asset_exists(asset.clone()) {
+				ensure!(
+					T::Assets::minimum_balance(asset.clone()).saturated_into::<u128>() ==
+						metadata.min_balance,
+					Error::<T>::AssetMinBalanceMismatch
+				);
+			} else {
+				Self::resolve_create(
+					asset_id.into(),
+					pallet_account.clone(),
+					metadata.min_balance,
+				)?;
+			}
+			<T::Assets as MutateMetadata<T::AccountId>>::set(
+				asset,
+				&pallet_account,
+				metadata.name.to_vec(),
+				metadata.symbol.to_vec(),
+				metadata.decimals,
+			)?;
+			<AssetRegistry<T>>::insert(asset_id, metadata.clone());
+			Self::deposit_event(Event::<T>::AssetMetadataUpdated { asset_id, metadata });
+			Ok(())
+		}
+
+		/// Registers alias location resolving to an existing asset, used when foreign chain
+		/// changes how its asset is addressed.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `alias`: Alias location of asset.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::add_asset_alias())]
+		pub fn add_asset_alias(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			alias: AssetId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<ParachainAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+			let alias = Self::canonicalise_asset(alias);
+			ensure!(
+				!Self::is_native_asset(&alias) && !<ParachainAssetIds<T>>::contains_key(alias),
+				Error::<T>::AssetLocationAlreadyMapped
+			);
+			<ParachainAssetIds<T>>::insert(alias, asset_id);
+			Self::deposit_event(Event::<T>::AssetAliasAdded { asset_id, alias });
+			Ok(())
+		}
+
+		/// Removes alias location of an asset. Canonical location of asset can't be removed.
+		///
+		/// # Parameters
+		///
+		/// * `alias`: Alias location of asset.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::remove_asset_alias())]
+		pub fn remove_asset_alias(origin: OriginFor<T>, alias: AssetId) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let alias = Self::canonicalise_asset(alias);
+			let asset_id =
+				<ParachainAssetIds<T>>::get(alias).ok_or(Error::<T>::AssetLocationNotMapped)?;
+			ensure!(
+				<ParachainAssets<T>>::get(asset_id) != Some(alias),
+				Error::<T>::CanonicalAssetLocation
+			);
+			<ParachainAssetIds<T>>::remove(alias);
+			Self::deposit_event(Event::<T>::AssetAliasRemoved { asset_id, alias });
+			Ok(())
+		}
+
+		/// Sets which of the locations mapped to an asset is used for outbound withdrawals.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `location`: Location already mapped to the asset.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_canonical_asset_location())]
+		pub fn set_canonical_asset_location(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			location: AssetId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let location = Self::canonicalise_asset(location);
+			ensure!(
+				<ParachainAssetIds<T>>::get(location) == Some(asset_id),
+				Error::<T>::AssetLocationNotMapped
+			);
+			<ParachainAssets<T>>::insert(asset_id, location);
+			if let AssetId::Concrete(location) = location {
+				<AssetRegistry<T>>::mutate(asset_id, |metadata| {
+					if let Some(metadata) = metadata {
+						metadata.location = location;
+					}
+				});
+			}
+			Self::deposit_event(Event::<T>::CanonicalAssetLocationUpdated { asset_id, location });
+			Ok(())
+		}
+
+		/// Sets location used for withdrawals of an abstract asset, `None` removes it.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `location`: Concrete location of asset used in outbound XCM transfers.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::set_abstract_asset_location())]
+		pub fn set_abstract_asset_location(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			location: Option<MultiLocation>,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let asset =
+				<ParachainAssets<T>>::get(asset_id).ok_or(Error::<T>::AssetNotRegistered)?;
+			ensure!(matches!(asset, AssetId::Abstract(_)), Error::<T>::AssetIsNotAbstract);
+			let location = location.and_then(|location| {
+				match Self::canonicalise_asset(AssetId::Concrete(location)) {
+					AssetId::Concrete(location) => Some(location),
+					AssetId::Abstract(_) => None,
+				}
+			});
+			<AbstractAssetLocations<T>>::set(asset_id, location);
+			Self::deposit_event(Event::<T>::AbstractAssetLocationUpdated { asset_id, location });
+			Ok(())
+		}
+
+		/// Marks asset as non-fungible, instances of the asset are then deposited to Thea one by
+		/// one and withdrawn back to its reserve chain.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `non_fungible`: Asset is a collection of non-fungible instances.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::set_asset_non_fungible())]
+		pub fn set_asset_non_fungible(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			non_fungible: bool,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<ParachainAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+			if non_fungible {
+				<NonFungibleAssets<T>>::insert(asset_id, ());
+			} else {
+				<NonFungibleAssets<T>>::remove(asset_id);
+			}
+			Self::deposit_event(Event::<T>::AssetFungibilityUpdated { asset_id, non_fungible });
+			Ok(())
+		}
+
+		/// Sets price of native token in asset, `None` removes it. XCM execution can be paid in
+		/// whitelisted foreign asset only if its price is set.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `price`: Amount of asset equivalent to one unit of native token.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_asset_price())]
+		pub fn set_asset_price(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			price: Option<FixedU128>,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<ParachainAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+			<AssetPrices<T>>::set(asset_id, price);
+			Self::deposit_event(Event::<T>::AssetPriceUpdated { asset_id, price });
+			Ok(())
+		}
+
+		/// Sets fixed fee rate of XCM execution paid in asset, `None` removes it.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Asset Id.
+		/// * `rate`: Amount of asset charged per second of execution and per byte of proof size.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_asset_fee_rate())]
+		pub fn set_asset_fee_rate(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			rate: Option<AssetFeeRate>,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			ensure!(<ParachainAssets<T>>::contains_key(asset_id), Error::<T>::AssetNotRegistered);
+			<AssetFeeRates<T>>::set(asset_id, rate);
+			Self::deposit_event(Event::<T>::AssetFeeRateUpdated { asset_id, rate });
+			Ok(())
+		}
+
+		/// Refunds withdrawal which couldn't be migrated to bounded storage back to Thea.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of unmigrated withdrawal.
+		/// * `recipient`: Account which will be credited on Thea side.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::refund_unmigrated_withdrawal())]
+		pub fn refund_unmigrated_withdrawal(
+			origin: OriginFor<T>,
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let withdrawal = <UnmigratedWithdrawals<T>>::take(sp_io::hashing::blake2_256(&id))
+				.ok_or(Error::<T>::UnmigratedWithdrawalNotFound)?;
+			Self::refund_to_thea(withdrawal.asset_id, withdrawal.amount, recipient.clone())?;
+			Self::deposit_event(Event::<T>::UnmigratedWithdrawalRefunded { id, recipient });
+			Ok(())
+		}
+
+		/// Transfers whole balance of an asset collected as XCM fees out of the fee pot.
+		///
+		/// # Parameters
+		///
+		/// * `asset_id`: Native or registered asset to be transferred.
+		/// * `to`: Recipient of the fees.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::transfer_fee_from_pot())]
+		pub fn transfer_fee_from_pot(
+			origin: OriginFor<T>,
+			asset_id: u128,
+			to: T::AccountId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let from = T::FeePotAccount::get();
+			let amount: u128 = if asset_id == T::NativeAssetId::get().into() {
+				T::Currency::reducible_balance(&from, Preservation::Preserve, Fortitude::Polite)
+					.saturated_into()
+			} else {
+				ensure!(
+					T::Assets::asset_exists(<T as Config>::AssetId::from(asset_id).into()),
+					Error::<T>::AssetNotRegistered
+				);
+				T::Assets::reducible_balance(
+					<T as Config>::AssetId::from(asset_id).into(),
+					&from,
+					Preservation::Preserve,
+					Fortitude::Polite,
+				)
+				.saturated_into()
+			};
+			Self::resolve_transfer(asset_id.into(), &from, &to, amount)?;
+			Self::deposit_event(Event::<T>::XcmFeeTransferred(to, asset_id, amount));
+			Ok(())
+		}
+
+		/// Refunds rejected withdrawal which couldn't be refunded automatically back to Thea. If
+		/// several rejected withdrawals share the same id, the oldest one is refunded.
+		///
+		/// # Parameters
+		///
+		/// * `id`: Thea withdrawal id of rejected withdrawal.
+		/// * `recipient`: Account which will be credited on Thea side.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::refund_rejected_withdrawal())]
+		pub fn refund_rejected_withdrawal(
+			origin: OriginFor<T>,
+			id: Vec<u8>,
+			recipient: T::AccountId,
+		) -> DispatchResult {
+			T::AssetCreateUpdateOrigin::ensure_origin(origin)?;
+			let key = sp_io::hashing::blake2_256(&id);
+			let mut withdrawals = <RejectedWithdrawals<T>>::take(key);
+			ensure!(!withdrawals.is_empty(), Error::<T>::RejectedWithdrawalNotFound);
+			let withdrawal = withdrawals.remove(0);
+			if !withdrawals.is_empty() {
+				<RejectedWithdrawals<T>>::insert(key, withdrawals);
+			}
+			Self::refund_to_thea(withdrawal.asset_id, withdrawal.amount, recipient.clone())?;
+			Self::deposit_event(Event::<T>::RejectedWithdrawalRefunded { id, recipient });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Convert<u128, Option<MultiLocation>> for Pallet<T> {
+		fn convert(asset_id: u128) -> Option<MultiLocation> {
+			Self::convert_asset_id_to_location(asset_id)
+		}
+	}
+
+	impl<T: Config> TransactAsset for Pallet<T> {
+		/// Generate Ingress Message for new Deposit
+		fn deposit_asset(
+			what: &MultiAsset,
+			who: &MultiLocation,
+			context: &XcmContext,
+		) -> xcm::latest::Result {
+			// Assets of rejected deposit are trapped and can be claimed once bridge is unpaused
+			if <InboundPaused<T>>::get() {
+				return Err(Self::trap_deposit(what, who, TrapCode::InboundPaused))
+			}
+			// Create approved deposit
+			let MultiAsset { id, fun } = what;
+			let recipient =
+				T::AccountIdConvert::convert_ref(who).map_err(|_| XcmError::FailedToDecode)?;
+			let asset_id = Self::get_asset_id(*id).ok_or(XcmError::AssetNotFound)?;
+			// Assets of frozen asset are trapped as well
+			if Self::is_asset_frozen(asset_id) {
+				return Err(Self::trap_deposit(what, who, TrapCode::AssetFrozen))
+			}
+			// Every non-fungible instance is deposited as one unit of its own asset id, instance
+			// is passed to Thea in extra data as well
+			let collection_id = asset_id;
+			let (asset_id, amount, instance) = match fun {
+				Fungibility::Fungible(amount) if !Self::is_non_fungible_asset(asset_id) =>
+					(asset_id, *amount, None),
+				Fungibility::NonFungible(instance) if Self::is_non_fungible_asset(asset_id) => (
+					Self::generate_non_fungible_instance_id(asset_id, *instance),
+					1u128,
+					Some(*instance),
+				),
+				_ => return Err(XcmError::AssetNotFound),
+			};
+			let extra =
+				DepositExtra { message_id: context.message_id, origin: context.origin, instance };
+			let deposit: Deposit<T::AccountId> = Deposit {
+				id: Self::new_random_id(),
+				recipient,
+				asset_id,
+				amount,
+				extra: extra.encode(),
+			};
+
+			let parachain_network_id = T::SubstrateNetworkId::get();
+			T::Executor::execute_withdrawals(parachain_network_id, sp_std::vec![deposit].encode())
+				.map_err(|_| Self::trap_deposit(what, who, TrapCode::TheaDepositFailed))?;
+			// Instance is only known once it's deposited on Thea
+			if let Some(instance) = instance {
+				<NonFungibleInstances<T>>::insert(asset_id, (collection_id, instance));
+			}
+			Self::deposit_event(Event::<T>::AssetDeposited(
+				Box::new(*who),
+				Box::new(what.clone()),
+				asset_id,
+				context.message_id,
+				context.origin.map(Box::new),
+			));
+			Ok(())
+		}
+
+		/// Burns/Lock asset from provided account.
+		fn withdraw_asset(
+			what: &MultiAsset,
+			who: &MultiLocation,
+			context: Option<&XcmContext>,
+		) -> sp_std::result::Result<Assets, XcmError> {
+			log::debug!(target:"xcm-helper","Withdrawing {:?} from {:?}, message id {:?}", what, who, context.map(|context| context.message_id));
+			let MultiAsset { id: _, fun } = what;
+			let who =
+				T::AccountIdConvert::convert_ref(who).map_err(|_| XcmError::FailedToDecode)?;
+			let pallet_account: T::AccountId =
+				T::AssetHandlerPalletId::get().into_account_truncating();
+			if let Fungibility::NonFungible(_) = fun {
+				let asset_id = Self::get_asset_id(what.id).ok_or(XcmError::AssetNotFound)?;
+				// Instances are not tracked locally, they can only be withdrawn by the pallet
+				// while executing withdrawals of non-fungible assets
+				ensure!(
+					Self::is_non_fungible_asset(asset_id) && who == pallet_account,
+					XcmError::NotWithdrawable
+				);
+				Self::deposit_event(Event::<T>::AssetWithdrawn(who, Box::new(what.clone())));
+				return Ok(what.clone().into())
+			}
+			let amount: u128 = Self::get_amount(fun).ok_or(XcmError::AssetNotFound)?;
+			let asset_id = Self::get_asset_id(what.id).ok_or(XcmError::AssetNotFound)?;
+			Self::resolver_withdraw(asset_id.into(), amount.saturated_into(), &who, pallet_account)
+				.map_err(|_| XcmError::FailedToTransactAsset("Failed to withdraw asset"))?;
+			Self::deposit_event(Event::<T>::AssetWithdrawn(who, Box::new(what.clone())));
+			Ok(what.clone().into())
+		}
+
+		/// Transfers Asset from source account to destination account
+		fn transfer_asset(
+			asset: &MultiAsset,
 			from: &MultiLocation,
 			to: &MultiLocation,
-			_context: &XcmContext,
+			context: &XcmContext,
 		) -> sp_std::result::Result<Assets, XcmError> {
+			log::debug!(target:"xcm-helper","Transferring {:?} from {:?} to {:?}, message id {:?}", asset, from, to, context.message_id);
 			let MultiAsset { id, fun } = asset;
 			let from =
 				T::AccountIdConvert::convert_ref(from).map_err(|_| XcmError::FailedToDecode)?;
 			let to = T::AccountIdConvert::convert_ref(to).map_err(|_| XcmError::FailedToDecode)?;
-			let amount: u128 = Self::get_amount(fun).ok_or(XcmError::Trap(101))?;
-			let asset_id = Self::generate_asset_id_for_parachain(*id);
+			let amount: u128 = Self::get_amount(fun).ok_or(XcmError::AssetNotFound)?;
+			let asset_id = Self::get_asset_id(*id).ok_or(XcmError::AssetNotFound)?;
 			Self::resolve_transfer(asset_id.into(), &from, &to, amount)
-				.map_err(|_| XcmError::Trap(102))?;
+				.map_err(|_| XcmError::FailedToTransactAsset("Failed to transfer asset"))?;
 			Ok(asset.clone().into())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Records reason of rejected deposit and returns error trapping its assets
+		fn trap_deposit(what: &MultiAsset, who: &MultiLocation, reason: TrapCode) -> XcmError {
+			Self::deposit_event(Event::<T>::DepositTrapped {
+				beneficiary: Box::new(*who),
+				asset: Box::new(what.clone()),
+				reason,
+			});
+			reason.into()
+		}
+
 		/// Generates a new random id for withdrawals
 		fn new_random_id() -> Vec<u8> {
 			let mut nonce = <RandomnessNonce<T>>::get();
@@ -518,6 +1955,144 @@ pub mod pallet {
 			entropy.to_vec()
 		}
 
+		/// Executes single withdrawal inside a storage transaction, so that either all changes
+		/// made by the withdrawal are committed or none of them.
+		fn execute_withdrawal(withdrawal: &BoundedWithdraw) -> Result<(), WithdrawalFailureReason> {
+			if withdrawal.is_blocked {
+				return Err(WithdrawalFailureReason::BlockedByCouncil)
+			}
+			if let Some(limit) = <AssetRateLimits<T>>::get(withdrawal.asset_id) {
+				ensure!(
+					withdrawal.amount <= limit.max_amount,
+					WithdrawalFailureReason::ExceedsRateLimit
+				);
+			}
+			let destination = VersionedMultiLocation::decode(&mut &withdrawal.destination[..])
+				.map_err(|_| WithdrawalFailureReason::DestinationDecodeFailed)?;
+			let route = with_transaction(|| {
+				let result = Self::do_execute_withdrawal(withdrawal, destination.clone());
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			})?;
+			Self::record_withdrawal_usage(withdrawal.asset_id, withdrawal.amount);
+			Self::deposit_event(Event::<T>::WithdrawalExecuted {
+				id: withdrawal.id.to_vec(),
+				asset_id: withdrawal.asset_id,
+				amount: withdrawal.amount,
+				destination: Box::new(destination),
+				route,
+			});
+			Ok(())
+		}
+
+		fn do_execute_withdrawal(
+			withdrawal: &BoundedWithdraw,
+			destination: VersionedMultiLocation,
+		) -> Result<WithdrawalRoute, WithdrawalFailureReason> {
+			if let Some((collection_id, instance)) =
+				<NonFungibleInstances<T>>::get(withdrawal.asset_id)
+			{
+				return Self::do_execute_non_fungible_withdrawal(
+					withdrawal,
+					collection_id,
+					instance,
+					destination,
+				)
+			}
+			// Collection itself is never credited on Thea, only its instances
+			if Self::is_non_fungible_asset(withdrawal.asset_id) {
+				return Err(WithdrawalFailureReason::InvalidNonFungibleInstance)
+			}
+			if Self::is_polkadex_parachain_destination(&destination) {
+				Self::handle_deposit(withdrawal.clone().into(), destination)
+					.map_err(WithdrawalFailureReason::LocalDepositFailed)?;
+				return Ok(WithdrawalRoute::Local)
+			}
+			let location = Self::convert_asset_id_to_location(withdrawal.asset_id)
+				.ok_or(WithdrawalFailureReason::UnknownAssetMapping)?;
+			let multi_asset = MultiAsset {
+				id: AssetId::Concrete(location),
+				fun: Fungibility::Fungible(withdrawal.amount),
+			};
+			let pallet_account: T::AccountId =
+				T::AssetHandlerPalletId::get().into_account_truncating();
+			// Mint
+			Self::resolver_deposit(
+				withdrawal.asset_id.into(),
+				withdrawal.amount,
+				&pallet_account,
+				pallet_account.clone(),
+				1u128,
+				pallet_account.clone(),
+			)
+			.map_err(|error| WithdrawalFailureReason::MintFailed(error.into()))?;
+			orml_xtokens::module::Pallet::<T>::transfer_multiassets(
+				RawOrigin::Signed(pallet_account).into(),
+				Box::new(multi_asset.into()),
+				0,
+				Box::new(destination),
+				cumulus_primitives_core::WeightLimit::Unlimited,
+			)
+			.map_err(|error| WithdrawalFailureReason::XcmTransferFailed(error.into()))?;
+			Ok(WithdrawalRoute::Xcm)
+		}
+
+		/// Sends instance of non-fungible asset held by the pallet account on its reserve chain
+		/// to the beneficiary on that chain. Instance is forgotten once it leaves this chain, so
+		/// it can't be withdrawn twice.
+		fn do_execute_non_fungible_withdrawal(
+			withdrawal: &BoundedWithdraw,
+			collection_id: u128,
+			instance: AssetInstance,
+			destination: VersionedMultiLocation,
+		) -> Result<WithdrawalRoute, WithdrawalFailureReason> {
+			ensure!(withdrawal.amount == 1, WithdrawalFailureReason::InvalidNonFungibleAmount);
+			let location = Self::convert_asset_id_to_location(collection_id)
+				.ok_or(WithdrawalFailureReason::UnknownAssetMapping)?;
+			let destination: MultiLocation = destination
+				.try_into()
+				.map_err(|_| WithdrawalFailureReason::DestinationDecodeFailed)?;
+			let asset = MultiAsset {
+				id: AssetId::Concrete(location),
+				fun: Fungibility::NonFungible(instance),
+			};
+			let reserve = <T as orml_xtokens::Config>::ReserveProvider::reserve(&asset)
+				.filter(|reserve| destination.chain_part().as_ref() == Some(reserve))
+				.ok_or(WithdrawalFailureReason::UnsupportedNonFungibleDestination)?;
+			let beneficiary = destination
+				.non_chain_part()
+				.ok_or(WithdrawalFailureReason::UnsupportedNonFungibleDestination)?;
+			let mut message: Xcm<<T as frame_system::Config>::RuntimeCall> = Xcm(vec![
+				Instruction::WithdrawAsset(asset.into()),
+				Instruction::InitiateReserveWithdraw {
+					assets: MultiAssetFilter::Wild(WildMultiAsset::AllCounted(1)),
+					reserve,
+					xcm: Xcm(vec![Instruction::DepositAsset {
+						assets: MultiAssetFilter::Wild(WildMultiAsset::AllCounted(1)),
+						beneficiary,
+					}]),
+				},
+			]);
+			let weight =
+				<T as orml_xtokens::Config>::Weigher::weight(&mut message).map_err(|_| {
+					WithdrawalFailureReason::XcmExecutionFailed(XcmError::WeightNotComputable)
+				})?;
+			let origin = <T as orml_xtokens::Config>::AccountIdToMultiLocation::convert(
+				Self::get_pallet_account(),
+			);
+			let hash = message.using_encoded(sp_io::hashing::blake2_256);
+			<T as orml_xtokens::Config>::XcmExecutor::execute_xcm_in_credit(
+				origin, message, hash, weight, weight,
+			)
+			.ensure_complete()
+			.map_err(WithdrawalFailureReason::XcmExecutionFailed)?;
+			<NonFungibleInstances<T>>::remove(withdrawal.asset_id);
+			Ok(WithdrawalRoute::Xcm)
+		}
+
 		/// Get Pallet Id
 		pub fn get_pallet_account() -> T::AccountId {
 			T::AssetHandlerPalletId::get().into_account_truncating()
@@ -588,25 +2163,71 @@ pub mod pallet {
 			}
 		}
 
-		/// Retrieves the existing assetid for given assetid or generates and stores a new assetid
+		/// Simplifies concrete asset location against `UniversalLocation` of this chain, so that
+		/// the same asset expressed relative to different anchors has a single representation,
+		/// e.g. `(2, GlobalConsensus(Polkadot), Parachain(1000))` becomes `(1, Parachain(1000))`
+		/// and `(1, Parachain(<own id>))` becomes `Here`.
+		pub fn canonicalise_asset(asset: AssetId) -> AssetId {
+			match asset {
+				AssetId::Concrete(mut location) => {
+					location.simplify(&<T as orml_xtokens::Config>::UniversalLocation::get());
+					AssetId::Concrete(location)
+				},
+				AssetId::Abstract(_) => asset,
+			}
+		}
+
+		/// Generates assetid for given assetid from its canonical form, doesn't touch the storage
 		pub fn generate_asset_id_for_parachain(asset: AssetId) -> u128 {
+			let asset = Self::canonicalise_asset(asset);
 			// Check if its native or not.
-			if asset ==
-				AssetId::Concrete(MultiLocation {
-					parents: 1,
-					interior: Junctions::X1(Parachain(T::ParachainId::get())),
-				}) {
+			if Self::is_native_asset(&asset) {
 				return T::NativeAssetId::get().into()
 			}
 			// If it's not native, then hash and generate the asset id
-			let asset_id = u128::from_be_bytes(sp_io::hashing::blake2_128(&asset.encode()[..]));
-			if !<ParachainAssets<T>>::contains_key(asset_id) {
-				// Store the mapping
-				<ParachainAssets<T>>::insert(asset_id, asset);
+			u128::from_be_bytes(sp_io::hashing::blake2_128(&asset.encode()[..]))
+		}
+
+		/// Generates asset id of an instance of non-fungible collection
+		pub fn generate_non_fungible_instance_id(
+			collection_id: u128,
+			instance: AssetInstance,
+		) -> u128 {
+			u128::from_be_bytes(sp_io::hashing::blake2_128(&(collection_id, instance).encode()[..]))
+		}
+
+		/// Returns assetid of given assetid if it's native or registered by governance
+		pub fn get_asset_id(asset: AssetId) -> Option<u128> {
+			let asset = Self::canonicalise_asset(asset);
+			if Self::is_native_asset(&asset) {
+				return Some(T::NativeAssetId::get().into())
 			}
+			<ParachainAssetIds<T>>::get(asset)
+		}
+
+		/// Returns assetid of registered asset or the one it will be registered with
+		fn get_or_generate_asset_id(asset: AssetId) -> u128 {
+			Self::get_asset_id(asset)
+				.unwrap_or_else(|| Self::generate_asset_id_for_parachain(asset))
+		}
+
+		/// Stores mapping of given assetid, used only by governance calls
+		fn register_asset(asset: AssetId) -> u128 {
+			let asset = Self::canonicalise_asset(asset);
+			if let Some(asset_id) = Self::get_asset_id(asset) {
+				return asset_id
+			}
+			let asset_id = Self::generate_asset_id_for_parachain(asset);
+			<ParachainAssets<T>>::insert(asset_id, asset);
+			<ParachainAssetIds<T>>::insert(asset, asset_id);
 			asset_id
 		}
 
+		/// Checks if canonical asset is native asset of this chain
+		fn is_native_asset(asset: &AssetId) -> bool {
+			*asset == AssetId::Concrete(MultiLocation::here())
+		}
+
 		/// Converts XCM::Fungibility into u128
 		pub fn get_amount(fun: &Fungibility) -> Option<u128> {
 			if let Fungibility::Fungible(amount) = fun {
@@ -616,31 +2237,506 @@ pub mod pallet {
 			}
 		}
 
-		/// Block Transaction to be Executed.
-		pub fn block_by_ele(block_no: T::BlockNumber, index: u32) -> DispatchResult {
-			let mut pending_withdrawals = <PendingWithdrawals<T>>::get(block_no);
-			let pending_withdrawal: &mut Withdraw =
-				pending_withdrawals.get_mut(index as usize).ok_or(Error::<T>::IndexNotFound)?;
-			pending_withdrawal.is_blocked = true;
-			<PendingWithdrawals<T>>::insert(block_no, pending_withdrawals);
-			Ok(())
+		/// Blocks or unblocks pending withdrawal with given Thea withdrawal id.
+		/// Held withdrawals can be blocked as well, block is applied once they are released.
+		pub fn set_withdrawal_blocked(id: &[u8], is_blocked: bool) -> DispatchResult {
+			let id = WithdrawalId::try_from(id.to_vec())
+				.map_err(|_| Error::<T>::WithdrawalNotPending)?;
+			if let Some(mut withdrawal) = <HeldWithdrawals<T>>::get(&id) {
+				withdrawal.is_blocked = is_blocked;
+				<HeldWithdrawals<T>>::insert(&id, withdrawal);
+				return Ok(())
+			}
+			let block_no =
+				<WithdrawalIndex<T>>::get(&id).ok_or(Error::<T>::WithdrawalNotPending)?;
+			<PendingWithdrawals<T>>::try_mutate(block_no, |pending_withdrawals| {
+				let pending_withdrawal = pending_withdrawals
+					.iter_mut()
+					.find(|withdrawal| withdrawal.id == id)
+					.ok_or(Error::<T>::WithdrawalNotPending)?;
+				pending_withdrawal.is_blocked = is_blocked;
+				Ok(())
+			})
 		}
 
-		/// Converts asset_id to XCM::MultiLocation
+		/// Converts asset_id to XCM::MultiLocation, abstract assets are converted to the location
+		/// set for their withdrawals
 		pub fn convert_asset_id_to_location(asset_id: u128) -> Option<MultiLocation> {
 			Self::assets_mapping(asset_id).and_then(|asset| match asset {
 				AssetId::Concrete(location) => Some(location),
-				AssetId::Abstract(_) => None,
+				AssetId::Abstract(_) => <AbstractAssetLocations<T>>::get(asset_id),
+			})
+		}
+
+		/// Converts Multilocation to u128, if asset is native or registered
+		pub fn convert_location_to_asset_id(location: MultiLocation) -> Option<u128> {
+			Self::get_asset_id(AssetId::Concrete(location))
+		}
+
+		pub fn insert_pending_withdrawal(
+			block_no: T::BlockNumber,
+			withdrawal: Withdraw,
+		) -> DispatchResult {
+			let withdrawal = BoundedWithdraw::try_from(withdrawal)
+				.map_err(|_| Error::<T>::WithdrawalTooLarge)?;
+			<WithdrawalIndex<T>>::insert(&withdrawal.id, block_no);
+			<PendingWithdrawals<T>>::insert(block_no, BoundedVec::truncate_from(vec![withdrawal]));
+			Ok(())
+		}
+
+		pub fn insert_held_withdrawal(withdrawal: Withdraw) -> DispatchResult {
+			let withdrawal = BoundedWithdraw::try_from(withdrawal)
+				.map_err(|_| Error::<T>::WithdrawalTooLarge)?;
+			<HeldWithdrawals<T>>::insert(withdrawal.id.clone(), withdrawal);
+			Ok(())
+		}
+
+		pub fn insert_failed_withdrawal(
+			block_no: T::BlockNumber,
+			withdrawal: Withdraw,
+			reason: WithdrawalFailureReason,
+		) -> DispatchResult {
+			let withdrawal = BoundedWithdraw::try_from(withdrawal)
+				.map_err(|_| Error::<T>::WithdrawalTooLarge)?;
+			let id = withdrawal.id.clone();
+			<FailedWithdrawals<T>>::try_append(
+				block_no,
+				FailedWithdrawal { withdraw: withdrawal, reason, attempted_at: block_no },
+			)
+			.map_err(|_| Error::<T>::FailedWithdrawalsLimitReached)?;
+			<FailedWithdrawalIndex<T>>::insert(&id, block_no);
+			Ok(())
+		}
+
+		/// Schedules withdrawals for execution starting from `block_no`. Withdrawals which don't
+		/// fit in a block are moved to the following blocks. Withdrawals at the end of the list
+		/// are executed first, so they are placed in the earliest block.
+		pub(crate) fn schedule_withdrawals(
+			mut block_no: T::BlockNumber,
+			mut withdrawals: Vec<BoundedWithdraw>,
+		) -> Weight {
+			let mut weight = Weight::zero();
+			while !withdrawals.is_empty() {
+				let scheduled = <PendingWithdrawals<T>>::mutate(block_no, |pending_withdrawals| {
+					let free_slots = (T::MaxPendingWithdrawalsPerBlock::get() as usize)
+						.saturating_sub(pending_withdrawals.len());
+					let split_at = withdrawals.len().saturating_sub(free_slots);
+					let scheduled = withdrawals.split_off(split_at);
+					for withdrawal in &scheduled {
+						<WithdrawalIndex<T>>::insert(&withdrawal.id, block_no);
+					}
+					let count = scheduled.len();
+					for withdrawal in scheduled {
+						// Can't fail, free slots are checked above
+						let _ = pending_withdrawals.try_push(withdrawal);
+					}
+					count
+				});
+				weight = weight.saturating_add(
+					T::DbWeight::get().reads_writes(1, (scheduled as u64).saturating_add(1)),
+				);
+				block_no = block_no.saturating_add(One::one());
+			}
+			weight
+		}
+
+		/// Returns block in which withdrawal should be executed or `None` if withdrawal has to be
+		/// held until released by Thea Council.
+		pub fn get_withdrawal_execution_block(
+			now: T::BlockNumber,
+			asset_id: u128,
+			amount: u128,
+		) -> Option<T::BlockNumber> {
+			let delay = match <AssetWithdrawalConfigs<T>>::get(asset_id) {
+				None => T::WithdrawalExecutionBlockDiff::get(),
+				Some(config) => match config.large_withdrawal_threshold {
+					Some(threshold) if amount > threshold => config.large_withdrawal_delay?,
+					_ => config.delay,
+				},
+			};
+			// Block `now` has already been processed, withdrawal can't be executed in it
+			Some(now.saturating_add(delay.max(One::one())))
+		}
+
+		/// Queues batch of withdrawals received from Thea for execution, large withdrawals are
+		/// held if required by asset config. Either the whole batch is queued or none of it.
+		fn queue_withdrawals(now: T::BlockNumber, withdrawals: Vec<Withdraw>) -> DispatchResult {
+			let withdrawals = withdrawals
+				.into_iter()
+				.map(BoundedWithdraw::try_from)
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(|_| Error::<T>::WithdrawalTooLarge)?;
+			let mut ids = BTreeSet::new();
+			for withdrawal in &withdrawals {
+				ensure!(
+					ids.insert(withdrawal.id.clone()) &&
+						!<WithdrawalIndex<T>>::contains_key(&withdrawal.id) &&
+						!<FailedWithdrawalIndex<T>>::contains_key(&withdrawal.id) &&
+						!<HeldWithdrawals<T>>::contains_key(&withdrawal.id),
+					Error::<T>::DuplicateWithdrawalId
+				);
+			}
+			let mut scheduled: BTreeMap<
+				T::BlockNumber,
+				BoundedVec<BoundedWithdraw, T::MaxPendingWithdrawalsPerBlock>,
+			> = BTreeMap::new();
+			let mut queued: Vec<(WithdrawalId, T::BlockNumber)> = Vec::new();
+			let mut held: Vec<BoundedWithdraw> = Vec::new();
+			for withdrawal in withdrawals {
+				match Self::get_withdrawal_execution_block(
+					now,
+					withdrawal.asset_id,
+					withdrawal.amount,
+				) {
+					Some(execute_at) => {
+						queued.push((withdrawal.id.clone(), execute_at));
+						scheduled
+							.entry(execute_at)
+							.or_insert_with(|| <PendingWithdrawals<T>>::get(execute_at))
+							.try_push(withdrawal)
+							.map_err(|_| Error::<T>::PendingWithdrawalsLimitReached)?;
+					},
+					None => held.push(withdrawal),
+				}
+			}
+			for (execute_at, pending_withdrawals) in scheduled {
+				<PendingWithdrawals<T>>::insert(execute_at, pending_withdrawals);
+			}
+			for (id, execute_at) in queued {
+				<WithdrawalIndex<T>>::insert(&id, execute_at);
+				Self::deposit_event(Event::<T>::WithdrawalQueued {
+					id: id.into_inner(),
+					execute_at,
+				});
+			}
+			for withdrawal in held {
+				let id = withdrawal.id.clone();
+				<HeldWithdrawals<T>>::insert(&id, withdrawal);
+				Self::deposit_event(Event::<T>::WithdrawalHeld { id: id.into_inner() });
+			}
+			Ok(())
+		}
+
+		/// Returns block in which current rate limit window of asset ends, if withdrawing
+		/// `amount` in block `now` would exceed the limit. Amounts above the limit itself are not
+		/// deferred, as they fail in any window.
+		pub fn get_rate_limit_window_end(
+			now: T::BlockNumber,
+			asset_id: u128,
+			amount: u128,
+		) -> Option<T::BlockNumber> {
+			let limit = <AssetRateLimits<T>>::get(asset_id)?;
+			// Withdrawal above the limit never fits into a window, so it's failed right away
+			if amount > limit.max_amount {
+				return None
+			}
+			let usage = <WithdrawalUsages<T>>::get(asset_id)?;
+			let window_end = usage.window_start.saturating_add(limit.window);
+			if now < window_end && usage.withdrawn.saturating_add(amount) > limit.max_amount {
+				Some(window_end)
+			} else {
+				None
+			}
+		}
+
+		/// Adds withdrawn amount to the current window of rate limited asset, starting a new
+		/// window if previous one has ended.
+		fn record_withdrawal_usage(asset_id: u128, amount: u128) {
+			let limit = match <AssetRateLimits<T>>::get(asset_id) {
+				Some(limit) => limit,
+				None => return,
+			};
+			let now = <frame_system::Pallet<T>>::block_number();
+			<WithdrawalUsages<T>>::mutate(asset_id, |usage| match usage {
+				Some(usage) if now < usage.window_start.saturating_add(limit.window) =>
+					usage.withdrawn = usage.withdrawn.saturating_add(amount),
+				_ => *usage = Some(WithdrawalUsage { window_start: now, withdrawn: amount }),
+			});
+		}
+
+		/// Checks if asset is bridged as non-fungible tokens.
+		pub fn is_non_fungible_asset(asset_id: u128) -> bool {
+			<NonFungibleAssets<T>>::contains_key(asset_id)
+		}
+
+		/// Checks if bridging of asset is frozen.
+		pub fn is_asset_frozen(asset_id: u128) -> bool {
+			// Instances of non-fungible assets are frozen together with their collection
+			<FrozenAssets<T>>::contains_key(asset_id) ||
+				<NonFungibleInstances<T>>::get(asset_id).map_or(false, |(collection_id, _)| {
+					<FrozenAssets<T>>::contains_key(collection_id)
+				})
+		}
+
+		/// Pauses bridge in given direction.
+		pub fn do_pause_bridge(direction: BridgeDirection) {
+			match direction {
+				BridgeDirection::Inbound => <InboundPaused<T>>::put(true),
+				BridgeDirection::Outbound => <OutboundPaused<T>>::put(true),
+			}
+			Self::deposit_event(Event::<T>::BridgePaused { direction });
+		}
+
+		/// Releases held withdrawal for execution in the next block.
+		pub fn do_release_held_withdrawal(id: &[u8]) -> DispatchResult {
+			let id =
+				WithdrawalId::try_from(id.to_vec()).map_err(|_| Error::<T>::WithdrawalNotHeld)?;
+			let withdrawal =
+				<HeldWithdrawals<T>>::take(&id).ok_or(Error::<T>::WithdrawalNotHeld)?;
+			let execute_at = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+			<PendingWithdrawals<T>>::try_append(execute_at, withdrawal)
+				.map_err(|_| Error::<T>::PendingWithdrawalsLimitReached)?;
+			<WithdrawalIndex<T>>::insert(&id, execute_at);
+			Self::deposit_event(Event::<T>::HeldWithdrawalReleased {
+				id: id.into_inner(),
+				execute_at,
+			});
+			Ok(())
+		}
+
+		/// Removes failed withdrawal with given Thea withdrawal id and returns it.
+		fn take_failed_withdrawal(id: &[u8]) -> Result<BoundedWithdraw, DispatchError> {
+			let id =
+				WithdrawalId::try_from(id.to_vec()).map_err(|_| Error::<T>::WithdrawalNotFailed)?;
+			let block_no =
+				<FailedWithdrawalIndex<T>>::take(&id).ok_or(Error::<T>::WithdrawalNotFailed)?;
+			<FailedWithdrawals<T>>::try_mutate_exists(block_no, |failed_withdrawals| {
+				let withdrawals =
+					failed_withdrawals.as_mut().ok_or(Error::<T>::WithdrawalNotFailed)?;
+				let index = withdrawals
+					.iter()
+					.position(|failed_withdrawal| failed_withdrawal.withdraw.id == id)
+					.ok_or(Error::<T>::WithdrawalNotFailed)?;
+				let failed_withdrawal = withdrawals.remove(index);
+				if withdrawals.is_empty() {
+					*failed_withdrawals = None;
+				}
+				Ok(failed_withdrawal.withdraw)
 			})
 		}
 
-		/// Converts Multilocation to u128
-		pub fn convert_location_to_asset_id(location: MultiLocation) -> u128 {
-			Self::generate_asset_id_for_parachain(AssetId::Concrete(location))
+		/// Queues failed withdrawal for execution in the next block.
+		pub fn do_retry_failed_withdrawal(id: &[u8]) -> DispatchResult {
+			let mut withdrawal = Self::take_failed_withdrawal(id)?;
+			// Retry is an explicit decision of governance, so council block is lifted as well
+			withdrawal.is_blocked = false;
+			let execute_at = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+			let id = withdrawal.id.clone();
+			<PendingWithdrawals<T>>::try_append(execute_at, withdrawal)
+				.map_err(|_| Error::<T>::PendingWithdrawalsLimitReached)?;
+			<WithdrawalIndex<T>>::insert(&id, execute_at);
+			Self::deposit_event(Event::<T>::FailedWithdrawalRequeued {
+				id: id.into_inner(),
+				execute_at,
+			});
+			Ok(())
+		}
+
+		/// Sends failed withdrawal back to Thea as a deposit for `recipient`.
+		pub fn do_refund_failed_withdrawal(id: &[u8], recipient: T::AccountId) -> DispatchResult {
+			let withdrawal = Self::take_failed_withdrawal(id)?;
+			Self::refund_to_thea(withdrawal.asset_id, withdrawal.amount, recipient.clone())?;
+			Self::deposit_event(Event::<T>::FailedWithdrawalRefunded {
+				id: withdrawal.id.into_inner(),
+				recipient,
+			});
+			Ok(())
+		}
+
+		/// Sends `amount` of asset back to Thea as a deposit for `recipient`.
+		fn refund_to_thea(asset_id: u128, amount: u128, recipient: T::AccountId) -> DispatchResult {
+			let deposit: Deposit<T::AccountId> = Deposit {
+				id: Self::new_random_id(),
+				recipient,
+				asset_id,
+				amount,
+				extra: Vec::new(),
+			};
+			T::Executor::execute_withdrawals(
+				T::SubstrateNetworkId::get(),
+				sp_std::vec![deposit].encode(),
+			)
+		}
+
+		/// Sends rejected withdrawals back to Thea as deposits for the accounts they were destined
+		/// to. Withdrawals without such account, or all of them if the refund fails, are kept in
+		/// `RejectedWithdrawals` until they are refunded by governance.
+		fn refund_rejected_withdrawals(withdrawals: Vec<Withdraw>) {
+			let (refundable, mut kept): (Vec<_>, Vec<_>) = withdrawals
+				.into_iter()
+				.map(|withdrawal| (Self::get_withdrawal_recipient(&withdrawal), withdrawal))
+				.partition(|(recipient, _)| recipient.is_some());
+			if !refundable.is_empty() {
+				let refunds: Vec<Deposit<T::AccountId>> = refundable
+					.iter()
+					.filter_map(|(recipient, withdrawal)| {
+						Some(Deposit {
+							id: Self::new_random_id(),
+							recipient: recipient.clone()?,
+							asset_id: withdrawal.asset_id,
+							amount: withdrawal.amount,
+							extra: Vec::new(),
+						})
+					})
+					.collect();
+				match T::Executor::execute_withdrawals(
+					T::SubstrateNetworkId::get(),
+					refunds.encode(),
+				) {
+					Ok(()) => Self::deposit_event(Event::<T>::RejectedWithdrawalsRefunded {
+						ids: refundable.into_iter().map(|(_, withdrawal)| withdrawal.id).collect(),
+					}),
+					Err(error) => {
+						log::error!(target:"xcm-helper","Refund of rejected withdrawals failed: {:?}", error);
+						kept.extend(refundable);
+					},
+				}
+			}
+			Self::keep_rejected_withdrawals(
+				kept.into_iter().map(|(_, withdrawal)| withdrawal).collect(),
+			);
+		}
+
+		/// Keeps rejected withdrawals in `RejectedWithdrawals` until they are refunded by
+		/// governance.
+		fn keep_rejected_withdrawals(withdrawals: Vec<Withdraw>) {
+			if withdrawals.is_empty() {
+				return
+			}
+			let ids = withdrawals
+				.into_iter()
+				.map(|withdrawal| {
+					let id = withdrawal.id.clone();
+					<RejectedWithdrawals<T>>::append(sp_io::hashing::blake2_256(&id), withdrawal);
+					id
+				})
+				.collect();
+			Self::deposit_event(Event::<T>::RejectedWithdrawalsKept { ids });
+		}
+
+		/// Removes failed withdrawal permanently.
+		pub fn do_discard_failed_withdrawal(id: &[u8]) -> DispatchResult {
+			let withdrawal = Self::take_failed_withdrawal(id)?;
+			Self::deposit_event(Event::<T>::FailedWithdrawalDiscarded {
+				id: withdrawal.id.into_inner(),
+			});
+			Ok(())
+		}
+
+		/// Returns pending withdrawals destined to `recipient` along with their execution block,
+		/// read from at most `limit` blocks following `start_key`. Key of the next page is
+		/// returned as well, if there may be more blocks to read.
+		pub fn get_pending_withdrawals_by_recipient(
+			recipient: &T::AccountId,
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(T::BlockNumber, Withdraw)>, Option<Vec<u8>>) {
+			let iter = match start_key {
+				Some(start_key) => <PendingWithdrawals<T>>::iter_from(start_key),
+				None => <PendingWithdrawals<T>>::iter(),
+			};
+			let (blocks, next_key) = Self::read_page(iter, limit);
+			let withdrawals = blocks
+				.into_iter()
+				.flat_map(|(block_no, withdrawals)| {
+					withdrawals.into_iter().map(move |withdrawal| (block_no, withdrawal.into()))
+				})
+				.filter(|(_, withdrawal)| {
+					Self::get_withdrawal_recipient(withdrawal).as_ref() == Some(recipient)
+				})
+				.collect();
+			(withdrawals, next_key)
 		}
 
-		pub fn insert_pending_withdrawal(block_no: T::BlockNumber, withdrawal: Withdraw) {
-			<PendingWithdrawals<T>>::insert(block_no, vec![withdrawal]);
+		/// Returns pending withdrawal with given Thea withdrawal id along with its execution block
+		pub fn get_pending_withdrawal_by_id(id: &[u8]) -> Option<(T::BlockNumber, Withdraw)> {
+			let id = WithdrawalId::try_from(id.to_vec()).ok()?;
+			let block_no = <WithdrawalIndex<T>>::get(&id)?;
+			<PendingWithdrawals<T>>::get(block_no)
+				.into_iter()
+				.find(|withdrawal| withdrawal.id == id)
+				.map(|withdrawal| (block_no, withdrawal.into()))
+		}
+
+		/// Returns failed withdrawals read from at most `limit` blocks following `start_key`.
+		/// Key of the next page is returned as well, if there may be more blocks to read.
+		pub fn get_failed_withdrawals_page(
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<FailedWithdrawal<T::BlockNumber>>, Option<Vec<u8>>) {
+			let iter = match start_key {
+				Some(start_key) => <FailedWithdrawals<T>>::iter_from(start_key),
+				None => <FailedWithdrawals<T>>::iter(),
+			};
+			let (blocks, next_key) = Self::read_page(iter, limit);
+			(blocks.into_iter().flat_map(|(_, withdrawals)| withdrawals).collect(), next_key)
+		}
+
+		/// Reads between one and `MAX_WITHDRAWAL_QUERY_BLOCKS` entries, at most `limit`, and
+		/// returns raw key of the last one if the limit is reached.
+		fn read_page<V>(
+			mut iter: PrefixIterator<(T::BlockNumber, V)>,
+			limit: u32,
+		) -> (Vec<(T::BlockNumber, V)>, Option<Vec<u8>>) {
+			let limit = limit.clamp(1, MAX_WITHDRAWAL_QUERY_BLOCKS) as usize;
+			let entries: Vec<_> = iter.by_ref().take(limit).collect();
+			let next_key =
+				if entries.len() == limit { Some(iter.last_raw_key().to_vec()) } else { None };
+			(entries, next_key)
+		}
+
+		/// Resolves account at the end of withdrawal destination
+		pub fn get_withdrawal_recipient(withdrawal: &Withdraw) -> Option<T::AccountId> {
+			let destination: MultiLocation =
+				VersionedMultiLocation::decode(&mut &withdrawal.destination[..])
+					.ok()?
+					.try_into()
+					.ok()?;
+			match destination.interior.last()? {
+				Junction::AccountId32 { id, .. } => T::AccountId::decode(&mut &id[..]).ok(),
+				_ => None,
+			}
+		}
+
+		/// Deposits asset taken from holding register as XCM fee into the fee pot and swaps it
+		/// into native token, returns amount of asset spent. Remaining asset stays in the fee pot.
+		fn do_swap_into_native_fee(
+			asset_id: u128,
+			amount: u128,
+			fee: u128,
+		) -> Result<u128, DispatchError> {
+			let fee_pot = T::FeePotAccount::get();
+			Self::deposit_into_fee_pot(asset_id, amount)?;
+			T::Swap::swap_tokens_for_exact_tokens(
+				&fee_pot,
+				asset_id,
+				T::NativeAssetId::get().into(),
+				fee,
+				amount,
+			)
+		}
+
+		/// Mints or unlocks XCM fee taken from holding register into the fee pot
+		fn deposit_into_fee_pot(asset_id: u128, amount: u128) -> DispatchResult {
+			let pallet_account = Self::get_pallet_account();
+			Self::resolver_deposit(
+				asset_id.into(),
+				amount,
+				&T::FeePotAccount::get(),
+				pallet_account.clone(),
+				1u128,
+				pallet_account,
+			)?;
+			Ok(())
+		}
+
+		/// Deposits XCM fee collected by the trader into the fee pot
+		fn do_take_revenue(revenue: &MultiAsset) -> DispatchResult {
+			let asset_id = Self::get_asset_id(revenue.id).ok_or(Error::<T>::AssetNotRegistered)?;
+			let amount = Self::get_amount(&revenue.fun).ok_or(Error::<T>::UnableToGetAssets)?;
+			Self::deposit_into_fee_pot(asset_id, amount)?;
+			Self::deposit_event(Event::<T>::XcmFeeCollected { asset_id, amount });
+			Ok(())
 		}
 
 		/// Converts Multilocation to AccountId
@@ -655,7 +2751,7 @@ pub mod pallet {
 		}
 
 		fn convert_location_to_asset_id(location: MultiLocation) -> Option<u128> {
-			Some(Self::convert_location_to_asset_id(location))
+			Self::convert_location_to_asset_id(location)
 		}
 	}
 
@@ -666,25 +2762,65 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> AssetFeeRateProvider for Pallet<T> {
+		fn get_fee_rate(asset_id: u128) -> Option<AssetFeeRate> {
+			<AssetFeeRates<T>>::get(asset_id)
+		}
+	}
+
+	impl<T: Config> AssetPriceProvider for Pallet<T> {
+		fn get_native_token_price(asset_id: u128) -> Option<FixedU128> {
+			<AssetPrices<T>>::get(asset_id)
+		}
+	}
+
+	impl<T: Config> TakeRevenue for Pallet<T> {
+		fn take_revenue(revenue: MultiAsset) {
+			if let Err(error) = Self::do_take_revenue(&revenue) {
+				log::error!(target:"xcm-helper","Failed to collect XCM fee {:?}: {:?}", revenue, error);
+			}
+		}
+	}
+
+	impl<T: Config> NativeFeeSwapper for Pallet<T> {
+		fn quote_native_fee(asset_id: u128, fee: u128) -> Option<u128> {
+			T::Swap::quote_price_tokens_for_exact_tokens(
+				asset_id,
+				T::NativeAssetId::get().into(),
+				fee,
+			)
+		}
+
+		fn swap_into_native_fee(asset_id: u128, amount: u128, fee: u128) -> DispatchResult {
+			let amount_in = with_transaction(|| {
+				let result = Self::do_swap_into_native_fee(asset_id, amount, fee);
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			})?;
+			Self::deposit_event(Event::<T>::XcmFeeSwapped { asset_id, amount_in, fee });
+			Ok(())
+		}
+	}
+
 	impl<T: Config> TheaIncomingExecutor for Pallet<T> {
 		fn execute_deposits(_: Network, deposits: Vec<u8>) {
 			let deposits = Vec::<Withdraw>::decode(&mut &deposits[..]).unwrap_or_default();
-			for deposit in deposits {
-				// Calculate the withdrawal execution delay
-				let withdrawal_execution_block: T::BlockNumber =
-					<frame_system::Pallet<T>>::block_number()
-						.saturated_into::<u32>()
-						.saturating_add(
-							T::WithdrawalExecutionBlockDiff::get().saturated_into::<u32>(),
-						)
-						.into();
-				// Queue the withdrawal for execution
-				<PendingWithdrawals<T>>::mutate(
-					withdrawal_execution_block,
-					|pending_withdrawals| {
-						pending_withdrawals.push(deposit);
-					},
-				);
+			let now = <frame_system::Pallet<T>>::block_number();
+			// Batch which can't be stored is rejected as a whole and refunded back to Thea
+			if let Err(reason) = Self::queue_withdrawals(now, deposits.clone()) {
+				log::error!(target:"xcm-helper","Withdrawals rejected: {:?}", reason);
+				let ids = deposits.iter().map(|deposit| deposit.id.clone()).collect();
+				Self::deposit_event(Event::<T>::WithdrawalsRejected { ids, reason });
+				if reason == Error::<T>::DuplicateWithdrawalId.into() {
+					// Refund of a replayed withdrawal would pay it out twice, so it's left to
+					// governance
+					Self::keep_rejected_withdrawals(deposits);
+				} else {
+					Self::refund_rejected_withdrawals(deposits);
+				}
 			}
 		}
 	}
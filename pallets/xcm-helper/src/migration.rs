@@ -0,0 +1,463 @@
+// This file is part of Polkadex.
+
+// Copyright (C) 2020-2023 Polkadex oü.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+//! Storage migrations for the XCM Helper pallet.
+
+pub mod v1 {
+	use crate::{Config, Pallet, WithdrawalFailureReason};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_std::{marker::PhantomData, vec::Vec};
+	use thea_primitives::types::Withdraw;
+
+	/// Failed withdrawal record as stored in v1.
+	#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+	pub struct FailedWithdrawal<BlockNumber> {
+		pub withdraw: Withdraw,
+		pub reason: WithdrawalFailureReason,
+		pub attempted_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub(super) type FailedWithdrawals<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::BlockNumber,
+		Vec<FailedWithdrawal<<T as frame_system::Config>::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// Converts bare `Withdraw` entries of `FailedWithdrawals` into `FailedWithdrawal` records.
+	/// Reason of these failures was never stored, so it is set to `Unknown`.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				log::info!(target:"xcm-helper","Skipping migration to v1, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let mut translated = 0u64;
+			<FailedWithdrawals<T>>::translate::<Vec<Withdraw>, _>(|block_no, withdrawals| {
+				translated = translated.saturating_add(1);
+				Some(
+					withdrawals
+						.into_iter()
+						.map(|withdraw| FailedWithdrawal {
+							withdraw,
+							reason: WithdrawalFailureReason::Unknown,
+							attempted_at: block_no,
+						})
+						.collect(),
+				)
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Migrated {} failed withdrawal entries to v1", translated);
+			T::DbWeight::get()
+				.reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+		}
+	}
+}
+
+pub mod v2 {
+	use super::v1;
+	use crate::{
+		BoundedWithdraw, Config, FailedWithdrawal, FailedWithdrawals, Pallet, PendingWithdrawals,
+		UnmigratedWhitelistedTokens, UnmigratedWithdrawals, WhitelistedTokens,
+	};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_runtime::traits::One;
+	use sp_std::{marker::PhantomData, vec::Vec};
+	use thea_primitives::types::Withdraw;
+
+	/// Moves `PendingWithdrawals`, `FailedWithdrawals` and `WhitelistedTokens` to bounded
+	/// storage. Entries which exceed the per block limits are moved to the following blocks,
+	/// withdrawals with fields exceeding the size limits are moved to `UnmigratedWithdrawals`
+	/// to be refunded and tokens exceeding the whitelist limit to `UnmigratedWhitelistedTokens`.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				log::info!(target:"xcm-helper","Skipping migration to v2, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			// Pending withdrawals
+			let max_pending = T::MaxPendingWithdrawalsPerBlock::get() as usize;
+			let mut pending_overflow: Vec<(T::BlockNumber, Vec<BoundedWithdraw>)> = Vec::new();
+			<PendingWithdrawals<T>>::translate::<Vec<Withdraw>, _>(|block_no, withdrawals| {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				let mut withdrawals = bound_withdrawals::<T>(withdrawals, &mut weight);
+				if withdrawals.is_empty() {
+					return None
+				}
+				// Withdrawals at the end are executed first, so they stay in this block
+				let overflow: Vec<_> =
+					withdrawals.drain(..withdrawals.len().saturating_sub(max_pending)).collect();
+				if !overflow.is_empty() {
+					pending_overflow.push((block_no, overflow));
+				}
+				Some(BoundedVec::truncate_from(withdrawals))
+			});
+			for (block_no, withdrawals) in pending_overflow {
+				log::info!(target:"xcm-helper","Moving {} pending withdrawals of block {:?} to following blocks", withdrawals.len(), block_no);
+				weight = weight.saturating_add(Pallet::<T>::schedule_withdrawals(
+					block_no.saturating_add(One::one()),
+					withdrawals,
+				));
+			}
+
+			// Failed withdrawals
+			let max_failed = T::MaxFailedWithdrawalsPerBlock::get() as usize;
+			let mut failed_overflow: Vec<(T::BlockNumber, Vec<FailedWithdrawal<T::BlockNumber>>)> =
+				Vec::new();
+			<FailedWithdrawals<T>>::translate::<Vec<v1::FailedWithdrawal<T::BlockNumber>>, _>(
+				|block_no, failed_withdrawals| {
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					let mut failed_withdrawals: Vec<_> = failed_withdrawals
+						.into_iter()
+						.filter_map(|failed| match BoundedWithdraw::try_from(failed.withdraw) {
+							Ok(withdraw) => Some(FailedWithdrawal {
+								withdraw,
+								reason: failed.reason,
+								attempted_at: failed.attempted_at,
+							}),
+							Err(withdraw) => {
+								keep_unmigrated_withdrawal::<T>(withdraw, &mut weight);
+								None
+							},
+						})
+						.collect();
+					if failed_withdrawals.is_empty() {
+						return None
+					}
+					if failed_withdrawals.len() > max_failed {
+						failed_overflow.push((block_no, failed_withdrawals.split_off(max_failed)));
+					}
+					Some(BoundedVec::truncate_from(failed_withdrawals))
+				},
+			);
+			for (mut block_no, mut failed_withdrawals) in failed_overflow {
+				log::info!(target:"xcm-helper","Moving {} failed withdrawals of block {:?} to following blocks", failed_withdrawals.len(), block_no);
+				while !failed_withdrawals.is_empty() {
+					block_no = block_no.saturating_add(One::one());
+					<FailedWithdrawals<T>>::mutate(block_no, |stored| {
+						let free_slots = max_failed.saturating_sub(stored.len());
+						let rest =
+							failed_withdrawals.split_off(free_slots.min(failed_withdrawals.len()));
+						for failed in failed_withdrawals.drain(..) {
+							// Can't fail, free slots are checked above
+							let _ = stored.try_push(failed);
+						}
+						failed_withdrawals = rest;
+					});
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				}
+			}
+
+			// Whitelisted tokens
+			let _ = <WhitelistedTokens<T>>::translate::<Vec<u128>, _>(|tokens| {
+				let tokens = tokens.unwrap_or_default();
+				let max_tokens = T::MaxWhitelistedTokens::get() as usize;
+				if tokens.len() > max_tokens {
+					log::error!(target:"xcm-helper","Keeping whitelisted tokens exceeding the limit aside: {:?}", &tokens[max_tokens..]);
+					<UnmigratedWhitelistedTokens<T>>::put(tokens[max_tokens..].to_vec());
+				}
+				Some(BoundedVec::truncate_from(tokens))
+			});
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Migrated withdrawals and whitelisted tokens to v2");
+			weight
+		}
+	}
+
+	/// Converts withdrawals to bounded ones, those exceeding the size limits are kept aside.
+	fn bound_withdrawals<T: Config>(
+		withdrawals: Vec<Withdraw>,
+		weight: &mut Weight,
+	) -> Vec<BoundedWithdraw> {
+		withdrawals
+			.into_iter()
+			.filter_map(|withdrawal| match BoundedWithdraw::try_from(withdrawal) {
+				Ok(withdrawal) => Some(withdrawal),
+				Err(withdrawal) => {
+					keep_unmigrated_withdrawal::<T>(withdrawal, weight);
+					None
+				},
+			})
+			.collect()
+	}
+
+	/// Keeps withdrawal exceeding the size limits in `UnmigratedWithdrawals`, so it can be
+	/// refunded back to Thea.
+	fn keep_unmigrated_withdrawal<T: Config>(withdrawal: Withdraw, weight: &mut Weight) {
+		log::error!(target:"xcm-helper","Keeping withdrawal exceeding size limits aside: {:?}", withdrawal);
+		<UnmigratedWithdrawals<T>>::insert(sp_io::hashing::blake2_256(&withdrawal.id), withdrawal);
+		*weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+}
+
+pub mod v3 {
+	use crate::{
+		AssetRegistry, Config, FailedWithdrawals, HeldWithdrawals, Pallet, ParachainAssets,
+		PendingWithdrawals, UnmigratedWhitelistedTokens, UnmigratedWithdrawals, WhitelistedTokens,
+	};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{fungibles::Inspect, OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, vec::Vec};
+
+	/// Removes `ParachainAssets` mappings stored for assets which were never whitelisted or
+	/// registered by governance. These were created by deposits and fee payments of arbitrary
+	/// locations before registration was restricted to governance. Mappings of assets which
+	/// were whitelisted once, i.e. exist in `Config::Assets`, kept aside by migration to v2 or
+	/// referenced by withdrawals are kept.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				log::info!(target:"xcm-helper","Skipping migration to v3, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let mut weight = T::DbWeight::get().reads_writes(3, 1);
+			let mut kept: BTreeSet<u128> = <WhitelistedTokens<T>>::get().into_iter().collect();
+			kept.extend(<UnmigratedWhitelistedTokens<T>>::get());
+			for withdrawals in <PendingWithdrawals<T>>::iter_values() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				kept.extend(withdrawals.iter().map(|withdrawal| withdrawal.asset_id));
+			}
+			for failed_withdrawals in <FailedWithdrawals<T>>::iter_values() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				kept.extend(failed_withdrawals.iter().map(|failed| failed.withdraw.asset_id));
+			}
+			for withdrawal in <HeldWithdrawals<T>>::iter_values() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				kept.insert(withdrawal.asset_id);
+			}
+			for withdrawal in <UnmigratedWithdrawals<T>>::iter_values() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				kept.insert(withdrawal.asset_id);
+			}
+			let stale: Vec<u128> = <ParachainAssets<T>>::iter_keys()
+				.filter(|asset_id| {
+					weight = weight.saturating_add(T::DbWeight::get().reads(3));
+					!kept.contains(asset_id) &&
+						!<AssetRegistry<T>>::contains_key(asset_id) &&
+						!T::Assets::asset_exists(<T as Config>::AssetId::from(*asset_id).into())
+				})
+				.collect();
+			for asset_id in &stale {
+				<ParachainAssets<T>>::remove(asset_id);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Pruned {} asset mappings which were never whitelisted", stale.len());
+			weight
+		}
+	}
+}
+
+pub mod v4 {
+	use crate::{
+		AssetRegistry, Config, Pallet, ParachainAssetIds, ParachainAssets, WhitelistedTokens,
+	};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_std::{marker::PhantomData, vec::Vec};
+	use xcm::v3::{AssetId, MultiLocation};
+
+	/// Stores `ParachainAssets` in canonical form and indexes them by canonical location.
+	/// Asset ids are kept as they are, since balances are stored under them. If several ids
+	/// map to the same canonical asset, the whitelisted one is used for new deposits.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				log::info!(target:"xcm-helper","Skipping migration to v4, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let whitelisted_tokens = <WhitelistedTokens<T>>::get();
+			let mut weight = T::DbWeight::get().reads_writes(2, 1);
+			let assets: Vec<(u128, AssetId)> = <ParachainAssets<T>>::iter().collect();
+			for (asset_id, asset) in assets {
+				weight = weight.saturating_add(T::DbWeight::get().reads(2));
+				let canonical = Pallet::<T>::canonicalise_asset(asset);
+				if canonical != asset {
+					<ParachainAssets<T>>::insert(asset_id, canonical);
+					weight = weight.saturating_add(T::DbWeight::get().writes(1));
+				}
+				if canonical == AssetId::Concrete(MultiLocation::here()) {
+					log::error!(target:"xcm-helper","Asset id {} is mapped to native asset", asset_id);
+					continue
+				}
+				match <ParachainAssetIds<T>>::get(canonical) {
+					Some(existing)
+						if whitelisted_tokens.contains(&existing) ||
+							!whitelisted_tokens.contains(&asset_id) =>
+					{
+						log::error!(target:"xcm-helper","Asset {:?} is mapped to {} and {}, using {}", canonical, existing, asset_id, existing);
+					},
+					_ => {
+						<ParachainAssetIds<T>>::insert(canonical, asset_id);
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+					},
+				}
+			}
+			<AssetRegistry<T>>::translate_values::<crate::AssetMetadata, _>(|mut metadata| {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				if let AssetId::Concrete(location) =
+					Pallet::<T>::canonicalise_asset(AssetId::Concrete(metadata.location))
+				{
+					metadata.location = location;
+				}
+				Some(metadata)
+			});
+			StorageVersion::new(4).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Migrated asset mappings to canonical locations");
+			weight
+		}
+	}
+}
+
+pub mod v5 {
+	use crate::{
+		Config, FailedWithdrawalIndex, FailedWithdrawals, Pallet, PendingWithdrawals,
+		WithdrawalIndex,
+	};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_std::marker::PhantomData;
+
+	/// Indexes pending and failed withdrawals by Thea withdrawal id. Withdrawals pending at the
+	/// upgrade were scheduled before `WithdrawalIndex` existed, so they couldn't be blocked by
+	/// id, and failed withdrawals are retried, refunded and discarded by id.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 4 {
+				log::info!(target:"xcm-helper","Skipping migration to v5, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let mut indexed = 0u64;
+			for (block_no, withdrawals) in <PendingWithdrawals<T>>::iter() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				for withdrawal in withdrawals {
+					<WithdrawalIndex<T>>::insert(&withdrawal.id, block_no);
+					indexed = indexed.saturating_add(1);
+				}
+			}
+			for (block_no, failed_withdrawals) in <FailedWithdrawals<T>>::iter() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				for failed_withdrawal in failed_withdrawals {
+					<FailedWithdrawalIndex<T>>::insert(&failed_withdrawal.withdraw.id, block_no);
+					indexed = indexed.saturating_add(1);
+				}
+			}
+			weight = weight.saturating_add(T::DbWeight::get().writes(indexed));
+			StorageVersion::new(5).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Indexed {} pending and failed withdrawals", indexed);
+			weight
+		}
+	}
+}
+
+pub mod v6 {
+	use crate::{Config, Pallet, ParachainAssets};
+	use frame_support::{
+		log,
+		pallet_prelude::*,
+		traits::{
+			fungibles::{Inspect, Mutate},
+			tokens::{Fortitude, Preservation},
+			Get, OnRuntimeUpgrade, StorageVersion,
+		},
+	};
+	use sp_runtime::SaturatedConversion;
+	use sp_std::{marker::PhantomData, vec::Vec};
+
+	/// Moves XCM fees paid in foreign assets from the pallet account into the fee pot. These
+	/// were collected before the fee pot existed. Native balance of the pallet account also
+	/// backs native tokens sent to other chains, so it stays in place and is transferred using
+	/// the deprecated `transfer_fee`.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 5 {
+				log::info!(target:"xcm-helper","Skipping migration to v6, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+			let pallet_account = Pallet::<T>::get_pallet_account();
+			let fee_pot = T::FeePotAccount::get();
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let asset_ids: Vec<u128> = <ParachainAssets<T>>::iter_keys().collect();
+			let mut moved = 0u64;
+			for asset_id in asset_ids {
+				weight = weight.saturating_add(T::DbWeight::get().reads(2));
+				let asset: <T as Config>::AssetId = asset_id.into();
+				let amount = T::Assets::reducible_balance(
+					asset.into(),
+					&pallet_account,
+					Preservation::Expendable,
+					Fortitude::Polite,
+				);
+				if amount.saturated_into::<u128>() == 0 {
+					continue
+				}
+				match T::Assets::transfer(
+					asset.into(),
+					&pallet_account,
+					&fee_pot,
+					amount,
+					Preservation::Expendable,
+				) {
+					Ok(_) => {
+						moved = moved.saturating_add(1);
+						weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 3));
+					},
+					Err(error) => {
+						log::error!(target:"xcm-helper","Failed to move fees of asset {} into fee pot: {:?}", asset_id, error);
+					},
+				}
+			}
+			StorageVersion::new(6).put::<Pallet<T>>();
+			log::info!(target:"xcm-helper","Moved fees of {} assets into fee pot", moved);
+			weight
+		}
+	}
+}
@@ -14,9 +14,15 @@
 // GNU General Public License for more details.
 
 use crate as xcm_helper;
+use crate::XcmHelperWeightInfo;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{
+		fungible, fungibles,
+		tokens::{Fortitude, Precision},
+		ConstU16, ConstU64,
+	},
+	weights::Weight,
 };
 use frame_system as system;
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key};
@@ -24,7 +30,9 @@ use sp_core::{ConstU32, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError, DispatchResult,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 use thea::ecdsa::{AuthorityId, AuthoritySignature};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -117,21 +125,54 @@ parameter_types! {
 	pub const AssetHandlerPalletId: PalletId = PalletId(*b"XcmHandl");
 	pub const WithdrawalExecutionBlockDiff: u32 = 1000;
 	pub const NativeAssetId: u128 = 100;
+	// Enough to execute two withdrawals per block
+	pub WithdrawalExecutionWeightLimit: Weight =
+		<crate::weights::WeightInfo<Test> as XcmHelperWeightInfo>::execute_withdrawal()
+			.saturating_mul(2);
+	pub const MaxPendingWithdrawalsPerBlock: u32 = 5;
+	pub const MaxFailedWithdrawalsPerBlock: u32 = 5;
+	pub const MaxWhitelistedTokens: u32 = 5;
+	pub const FeePotAccount: u64 = 500;
+	// Makes `MockExecutor` fail outgoing messages
+	pub static ExecutorFails: bool = false;
+}
+
+/// Sends outgoing messages through Thea unless `ExecutorFails` is set
+pub struct MockExecutor;
+
+impl thea_primitives::TheaOutgoingExecutor for MockExecutor {
+	fn execute_withdrawals(
+		network: thea_primitives::Network,
+		withdrawals: Vec<u8>,
+	) -> DispatchResult {
+		if ExecutorFails::get() {
+			return Err(DispatchError::Other("Executor failed"))
+		}
+		<TheaMessageHandler as thea_primitives::TheaOutgoingExecutor>::execute_withdrawals(
+			network,
+			withdrawals,
+		)
+	}
 }
 
 impl xcm_helper::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type AccountIdConvert = ();
+	type AccountIdConvert = AccountIdConverter;
 	type Assets = Assets;
 	type AssetId = u128;
 	type Currency = Balances;
 	type AssetCreateUpdateOrigin = EnsureRoot<Self::AccountId>;
-	type Executor = TheaMessageHandler;
+	type Executor = MockExecutor;
 	type AssetHandlerPalletId = AssetHandlerPalletId;
 	type WithdrawalExecutionBlockDiff = WithdrawalExecutionBlockDiff;
-	type ParachainId = ();
 	type SubstrateNetworkId = ();
 	type NativeAssetId = NativeAssetId;
+	type WithdrawalExecutionWeightLimit = WithdrawalExecutionWeightLimit;
+	type MaxPendingWithdrawalsPerBlock = MaxPendingWithdrawalsPerBlock;
+	type MaxFailedWithdrawalsPerBlock = MaxFailedWithdrawalsPerBlock;
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+	type Swap = MockSwap;
+	type FeePotAccount = FeePotAccount;
 	type WeightInfo = crate::weights::WeightInfo<Test>;
 }
 
@@ -171,7 +212,8 @@ parameter_type_with_key! {
 }
 
 use cumulus_primitives_core::{GlobalConsensus, InteriorMultiLocation, Parachain};
-use xcm::latest::MultiLocation;
+use parity_scale_codec::Decode;
+use xcm::latest::{Junction, Junctions, MultiLocation};
 use xcm_builder::{
 	test_utils::{NetworkId, X2},
 	FixedWeightBounds,
@@ -187,6 +229,21 @@ parameter_types! {
 	pub UniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(RelayNetwork::get()), Parachain(2040));
 }
 
+/// Converts local `AccountId32` locations to mock account ids
+pub struct AccountIdConverter;
+
+impl xcm_executor::traits::Convert<MultiLocation, u64> for AccountIdConverter {
+	fn convert(location: MultiLocation) -> Result<u64, MultiLocation> {
+		match location {
+			MultiLocation {
+				parents: 0,
+				interior: Junctions::X1(Junction::AccountId32 { id, .. }),
+			} => u64::decode(&mut &id[..]).map_err(|_| location),
+			_ => Err(location),
+		}
+	}
+}
+
 impl orml_xtokens::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = u128;
@@ -204,6 +261,78 @@ impl orml_xtokens::Config for Test {
 	type UniversalLocation = UniversalLocation;
 }
 
+thread_local! {
+	/// Reserves of pools used by `MockSwap`, keyed by asset in and asset out
+	static POOLS: RefCell<BTreeMap<(u128, u128), (u128, u128)>> = RefCell::new(BTreeMap::new());
+}
+
+/// Constant-product AMM without swap fee
+pub struct MockSwap;
+
+impl MockSwap {
+	/// Creates pool swapping `asset_in` into `asset_out` with provided reserves
+	pub fn create_pool(asset_in: u128, asset_out: u128, reserve_in: u128, reserve_out: u128) {
+		POOLS.with(|pools| {
+			pools.borrow_mut().insert((asset_in, asset_out), (reserve_in, reserve_out))
+		});
+	}
+
+	/// Returns reserves of pool swapping `asset_in` into `asset_out`
+	pub fn get_reserves(asset_in: u128, asset_out: u128) -> Option<(u128, u128)> {
+		POOLS.with(|pools| pools.borrow().get(&(asset_in, asset_out)).copied())
+	}
+}
+
+impl xcm_helper::Swap<u64> for MockSwap {
+	fn quote_price_tokens_for_exact_tokens(
+		asset_in: u128,
+		asset_out: u128,
+		amount_out: u128,
+	) -> Option<u128> {
+		let (reserve_in, reserve_out) = Self::get_reserves(asset_in, asset_out)?;
+		if amount_out >= reserve_out {
+			return None
+		}
+		// Rounded up, so the product of reserves never decreases
+		Some(reserve_in.saturating_mul(amount_out) / (reserve_out - amount_out) + 1)
+	}
+
+	fn swap_tokens_for_exact_tokens(
+		who: &u64,
+		asset_in: u128,
+		asset_out: u128,
+		amount_out: u128,
+		amount_in_max: u128,
+	) -> Result<u128, DispatchError> {
+		let amount_in = Self::quote_price_tokens_for_exact_tokens(asset_in, asset_out, amount_out)
+			.ok_or(DispatchError::Other("No pool"))?;
+		if amount_in > amount_in_max {
+			return Err(DispatchError::Other("Slippage exceeded"))
+		}
+		<Assets as fungibles::Mutate<u64>>::burn_from(
+			asset_in,
+			who,
+			amount_in,
+			Precision::Exact,
+			Fortitude::Force,
+		)?;
+		if asset_out == NativeAssetId::get() {
+			<Balances as fungible::Mutate<u64>>::mint_into(who, amount_out)?;
+		} else {
+			<Assets as fungibles::Mutate<u64>>::mint_into(asset_out, who, amount_out)?;
+		}
+		POOLS.with(|pools| {
+			if let Some((reserve_in, reserve_out)) =
+				pools.borrow_mut().get_mut(&(asset_in, asset_out))
+			{
+				*reserve_in = reserve_in.saturating_add(amount_in);
+				*reserve_out = reserve_out.saturating_sub(amount_out);
+			}
+		});
+		Ok(amount_in)
+	}
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
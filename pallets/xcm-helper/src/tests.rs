@@ -13,12 +13,39 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
 // GNU General Public License for more details.
 
-use crate::{mock::*, Error, PendingWithdrawals};
-use frame_support::{assert_noop, assert_ok, traits::Currency, BoundedVec};
+use crate::{
+	mock::*, AssetFeeRate, AssetFeeRateProvider, AssetMetadata, AssetPriceProvider, AssetRateLimit,
+	AssetRateLimits, AssetWithdrawalConfig, AssetWithdrawalConfigs, BoundedWithdraw,
+	BridgeDirection, Error, FailedWithdrawal, FailedWithdrawals, FrozenAssets, NativeFeeSwapper,
+	NonFungibleInstances, ParachainAssets, PendingWithdrawals, RejectedWithdrawals, TrapCode,
+	UnmigratedWhitelistedTokens, UnmigratedWithdrawals, WhitelistedTokens,
+	WithdrawalDeferralReason, WithdrawalFailureReason, WithdrawalId, WithdrawalIndex,
+	WithdrawalRoute, WithdrawalUsage, WithdrawalUsages,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{metadata::Inspect as InspectMetadata, Create, Inspect, Mutate},
+		Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use parity_scale_codec::{Decode, Encode};
 use sp_core::ByteArray;
-use sp_runtime::{traits::AccountIdConversion, DispatchError, SaturatedConversion};
-use thea_primitives::{types::Withdraw, Message, ValidatorSetId};
-use xcm::latest::{AssetId, MultiLocation};
+use sp_runtime::{
+	traits::AccountIdConversion, DispatchError, FixedPointNumber, FixedU128, SaturatedConversion,
+};
+use thea_primitives::{types::Withdraw, Message, TheaIncomingExecutor, ValidatorSetId};
+use xcm::{
+	latest::{
+		AssetId, AssetInstance, Error as XcmError, Fungibility, Junction, Junctions, MultiAsset,
+		MultiLocation, NetworkId, XcmContext,
+	},
+	VersionedMultiLocation,
+};
+use xcm_builder::TakeRevenue;
+use xcm_executor::traits::{Convert, TransactAsset};
 
 #[test]
 fn test_whitelist_token_returns_ok() {
@@ -104,6 +131,60 @@ fn test_transfer_fee_returns_ok() {
 	});
 }
 
+#[test]
+fn test_transfer_fee_from_pot_returns_ok() {
+	new_test_ext().execute_with(|| {
+		let recipient = 1;
+		let _ = Balances::deposit_creating(
+			&FeePotAccount::get(),
+			5_000_000_000_000_000_000_000u128.saturated_into(),
+		);
+		assert_ok!(XcmHelper::transfer_fee_from_pot(
+			RuntimeOrigin::root(),
+			NativeAssetId::get(),
+			recipient
+		));
+		assert_eq!(Balances::free_balance(recipient), 4999999999000000000000u128.saturated_into());
+	});
+}
+
+#[test]
+fn test_take_revenue_deposits_fee_into_fee_pot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		XcmHelper::take_revenue(MultiAsset { id: asset, fun: Fungibility::Fungible(1_000) });
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::XcmFeeCollected {
+			asset_id,
+			amount: 1_000,
+		}));
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &FeePotAccount::get()), 1_000);
+		// Fee paid in unknown asset is dropped
+		let unknown_asset =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		XcmHelper::take_revenue(MultiAsset {
+			id: unknown_asset,
+			fun: Fungibility::Fungible(1_000),
+		});
+		assert_noop!(
+			XcmHelper::transfer_fee_from_pot(
+				RuntimeOrigin::root(),
+				XcmHelper::generate_asset_id_for_parachain(unknown_asset),
+				1
+			),
+			Error::<Test>::AssetNotRegistered
+		);
+		// Minimum balance of the asset is kept in the fee pot
+		assert_ok!(XcmHelper::transfer_fee_from_pot(RuntimeOrigin::root(), asset_id, 1));
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &1), 999);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::XcmFeeTransferred(
+			1, asset_id, 999,
+		)));
+	});
+}
+
 #[test]
 fn test_transfer_fee_with_bad_origin_will_return_bad_origin_error() {
 	new_test_ext().execute_with(|| {
@@ -118,41 +199,1744 @@ fn test_transfer_fee_with_bad_origin_will_return_bad_origin_error() {
 			XcmHelper::transfer_fee(RuntimeOrigin::none(), recipient),
 			DispatchError::BadOrigin
 		);
+		assert_noop!(
+			XcmHelper::transfer_fee_from_pot(
+				RuntimeOrigin::none(),
+				NativeAssetId::get(),
+				recipient
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn test_retry_failed_withdrawal_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.is_blocked = true;
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			withdrawal.clone(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_eq!(XcmHelper::get_failed_withdrawal_block(withdrawal_id(&[1; 32])), Some(5));
+		assert_ok!(XcmHelper::retry_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32]));
+		assert!(<FailedWithdrawals<Test>>::get(5).is_empty());
+		assert_eq!(XcmHelper::get_failed_withdrawal_block(withdrawal_id(&[1; 32])), None);
+		withdrawal.is_blocked = false;
+		assert_eq!(<PendingWithdrawals<Test>>::get(11), pending(vec![withdrawal]));
+	});
+}
+
+#[test]
+fn test_retry_failed_withdrawal_with_unknown_id_returns_withdrawal_not_failed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_noop!(
+			XcmHelper::retry_failed_withdrawal(RuntimeOrigin::root(), vec![2; 32]),
+			Error::<Test>::WithdrawalNotFailed
+		);
+		assert_noop!(
+			XcmHelper::retry_failed_withdrawal(RuntimeOrigin::root(), vec![1; 1000]),
+			Error::<Test>::WithdrawalNotFailed
+		);
+	});
+}
+
+#[test]
+fn test_refund_failed_withdrawal_returns_ok() {
+	new_test_ext().execute_with(|| {
+		let first_withdrawal = get_failed_withdrawal();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 32];
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			first_withdrawal,
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			sec_withdrawal.clone(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(XcmHelper::refund_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32], 1));
+		let failed_withdrawals = <FailedWithdrawals<Test>>::get(5);
+		assert_eq!(failed_withdrawals.len(), 1);
+		assert_eq!(failed_withdrawals[0].withdraw, bounded(sec_withdrawal));
+		// Removing a withdrawal doesn't change how the others are addressed
+		assert_ok!(XcmHelper::discard_failed_withdrawal(RuntimeOrigin::root(), vec![2; 32]));
+		assert!(!<FailedWithdrawals<Test>>::contains_key(5));
+	});
+}
+
+#[test]
+fn test_discard_failed_withdrawal_returns_ok() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_ok!(XcmHelper::discard_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32]));
+		assert!(!<FailedWithdrawals<Test>>::contains_key(5));
+		assert_noop!(
+			XcmHelper::discard_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32]),
+			Error::<Test>::WithdrawalNotFailed
+		);
+	});
+}
+
+#[test]
+fn test_failed_withdrawal_calls_with_bad_origin_will_return_bad_origin_error() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		assert_noop!(
+			XcmHelper::retry_failed_withdrawal(RuntimeOrigin::signed(1), vec![1; 32]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmHelper::refund_failed_withdrawal(RuntimeOrigin::signed(1), vec![1; 32], 1),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmHelper::discard_failed_withdrawal(RuntimeOrigin::signed(1), vec![1; 32]),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn test_on_initialize_carries_over_withdrawals_exceeding_weight_limit() {
+	new_test_ext().execute_with(|| {
+		let withdrawals = vec![get_failed_withdrawal(); 3];
+		<PendingWithdrawals<Test>>::insert(1, pending(withdrawals));
+		XcmHelper::on_initialize(1);
+		// Weight limit allows only two withdrawals per block
+		assert!(<PendingWithdrawals<Test>>::get(1).is_empty());
+		assert_eq!(<FailedWithdrawals<Test>>::get(1).len(), 2);
+		assert_eq!(<PendingWithdrawals<Test>>::get(2).len(), 1);
+		XcmHelper::on_initialize(2);
+		assert!(<PendingWithdrawals<Test>>::get(2).is_empty());
+		assert_eq!(<FailedWithdrawals<Test>>::get(2).len(), 1);
 	});
 }
 
 #[test]
-fn test_block_by_ele() {
+fn test_on_initialize_rolls_back_minted_tokens_when_xcm_transfer_fails() {
 	new_test_ext().execute_with(|| {
-		let first_withdrawal = Withdraw {
+		let asset_id = 1;
+		let pallet_account = XcmHelper::get_pallet_account();
+		assert_ok!(<Assets as Create<u64>>::create(asset_id, pallet_account, true, 1));
+		<ParachainAssets<Test>>::insert(asset_id, AssetId::Concrete(MultiLocation::parent()));
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::Parachain(1000)),
+		));
+		let withdrawal = Withdraw {
 			id: Vec::new(),
-			asset_id: 1,
-			amount: 1,
-			destination: vec![],
+			asset_id,
+			amount: 1_000_000_000_000,
+			destination: destination.encode(),
 			is_blocked: false,
 			extra: vec![],
 		};
-		let sec_withdrawal = Withdraw {
-			id: Vec::new(),
-			asset_id: 2,
-			amount: 2,
-			destination: vec![],
-			is_blocked: false,
-			extra: vec![],
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		// Mock has no xcm executor, so transfer fails and mint is reverted
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &pallet_account), 0);
+		let failed_withdrawals = <FailedWithdrawals<Test>>::get(1);
+		assert_eq!(failed_withdrawals.len(), 1);
+		assert_eq!(failed_withdrawals[0].withdraw, bounded(withdrawal));
+		assert!(matches!(
+			failed_withdrawals[0].reason,
+			WithdrawalFailureReason::XcmTransferFailed(_)
+		));
+	});
+}
+
+#[test]
+fn test_on_initialize_stores_failure_reason_and_emits_event() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let withdrawal = get_failed_withdrawal();
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		let expected_failed_withdrawal = FailedWithdrawal {
+			withdraw: bounded(withdrawal.clone()),
+			reason: WithdrawalFailureReason::DestinationDecodeFailed,
+			attempted_at: 1,
 		};
-		<PendingWithdrawals<Test>>::insert(1, vec![first_withdrawal, sec_withdrawal]);
-		assert_ok!(XcmHelper::block_by_ele(1, 1));
-		let actual_withdrawals = <PendingWithdrawals<Test>>::get(1);
-		let expected_withdraw = Withdraw {
-			id: Vec::new(),
-			asset_id: 2,
-			amount: 2,
-			destination: vec![],
-			is_blocked: true,
-			extra: vec![],
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(1).into_inner(),
+			vec![expected_failed_withdrawal]
+		);
+		assert_eq!(XcmHelper::get_failed_withdrawal_block(withdrawal_id(&withdrawal.id)), Some(1));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalFailed {
+			id: withdrawal.id,
+			reason: WithdrawalFailureReason::DestinationDecodeFailed,
+		}));
+	});
+}
+
+#[test]
+fn test_on_initialize_executes_local_withdrawal_and_emits_event() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset_id = 1;
+		let pallet_account = XcmHelper::get_pallet_account();
+		assert_ok!(<Assets as Create<u64>>::create(asset_id, pallet_account, true, 1));
+		let account_bytes = [1u8; 32];
+		let recipient = u64::decode(&mut &account_bytes[..]).unwrap();
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: account_bytes }),
+		));
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.destination = destination.encode();
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &recipient), withdrawal.amount);
+		assert!(<FailedWithdrawals<Test>>::get(1).is_empty());
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalExecuted {
+			id: withdrawal.id,
+			asset_id,
+			amount: withdrawal.amount,
+			destination: Box::new(destination),
+			route: WithdrawalRoute::Local,
+		}));
+	});
+}
+
+#[test]
+fn test_execute_deposits_queues_withdrawals_and_emits_event() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let withdrawal = get_failed_withdrawal();
+		XcmHelper::execute_deposits(1, vec![withdrawal.clone()].encode());
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001), pending(vec![withdrawal.clone()]));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&withdrawal.id)), Some(1001));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalQueued {
+			id: withdrawal.id,
+			execute_at: 1001,
+		}));
+	});
+}
+
+#[test]
+fn test_pending_withdrawal_queries_return_expected_withdrawals() {
+	new_test_ext().execute_with(|| {
+		let account_bytes = [1u8; 32];
+		let recipient = u64::decode(&mut &account_bytes[..]).unwrap();
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X2(
+				Junction::Parachain(1000),
+				Junction::AccountId32 { network: None, id: account_bytes },
+			),
+		));
+		let mut first_withdrawal = get_failed_withdrawal();
+		first_withdrawal.destination = destination.encode();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 32];
+		<PendingWithdrawals<Test>>::insert(
+			5,
+			pending(vec![first_withdrawal.clone(), sec_withdrawal.clone()]),
+		);
+		assert_eq!(
+			XcmHelper::get_pending_withdrawals_by_recipient(&recipient, None, 10),
+			(vec![(5, first_withdrawal)], None)
+		);
+		<WithdrawalIndex<Test>>::insert(withdrawal_id(&sec_withdrawal.id), 5);
+		assert_eq!(XcmHelper::get_pending_withdrawal_by_id(&[2; 32]), Some((5, sec_withdrawal)));
+		assert_eq!(XcmHelper::get_pending_withdrawal_by_id(&[3; 32]), None);
+	});
+}
+
+#[test]
+fn test_withdrawal_queries_are_paginated_by_block() {
+	new_test_ext().execute_with(|| {
+		for block_no in 1..=3u64 {
+			let mut withdrawal = get_failed_withdrawal();
+			withdrawal.id = vec![block_no as u8; 32];
+			assert_ok!(XcmHelper::insert_failed_withdrawal(
+				block_no,
+				withdrawal,
+				WithdrawalFailureReason::BlockedByCouncil,
+			));
+		}
+		let (first_page, next_key) = XcmHelper::get_failed_withdrawals_page(None, 2);
+		assert_eq!(first_page.len(), 2);
+		let (last_page, next_key) = XcmHelper::get_failed_withdrawals_page(next_key, 2);
+		assert_eq!(last_page.len(), 1);
+		assert_eq!(next_key, None);
+		let mut attempted_at: Vec<u64> = first_page
+			.into_iter()
+			.chain(last_page)
+			.map(|failed_withdrawal| failed_withdrawal.attempted_at)
+			.collect();
+		attempted_at.sort();
+		assert_eq!(attempted_at, vec![1, 2, 3]);
+		// Pages never read more than the maximum number of blocks
+		for block_no in 4..=200u64 {
+			<PendingWithdrawals<Test>>::insert(block_no, pending(vec![get_failed_withdrawal()]));
+		}
+		let (_, next_key) = XcmHelper::get_pending_withdrawals_by_recipient(&1, None, u32::MAX);
+		assert!(next_key.is_some());
+	});
+}
+
+#[test]
+fn test_set_withdrawal_blocked_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let first_withdrawal = get_failed_withdrawal();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 32];
+		XcmHelper::execute_deposits(
+			1,
+			vec![first_withdrawal.clone(), sec_withdrawal.clone()].encode(),
+		);
+		assert_ok!(XcmHelper::set_withdrawal_blocked(&sec_withdrawal.id, true));
+		let pending_withdrawals = <PendingWithdrawals<Test>>::get(1001);
+		assert!(!pending_withdrawals[0].is_blocked);
+		assert!(pending_withdrawals[1].is_blocked);
+		assert_ok!(XcmHelper::set_withdrawal_blocked(&sec_withdrawal.id, false));
+		assert!(!<PendingWithdrawals<Test>>::get(1001)[1].is_blocked);
+	});
+}
+
+#[test]
+fn test_set_withdrawal_blocked_for_executed_withdrawal_returns_withdrawal_not_pending() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let withdrawal = get_failed_withdrawal();
+		XcmHelper::execute_deposits(1, vec![withdrawal.clone()].encode());
+		XcmHelper::on_initialize(1001);
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&withdrawal.id)), None);
+		assert_noop!(
+			XcmHelper::set_withdrawal_blocked(&withdrawal.id, true),
+			Error::<Test>::WithdrawalNotPending
+		);
+	});
+}
+
+#[test]
+fn test_whitelist_token_with_full_whitelist_returns_limit_reached() {
+	new_test_ext().execute_with(|| {
+		<WhitelistedTokens<Test>>::put(BoundedVec::truncate_from(vec![1, 2, 3, 4, 5]));
+		let token = AssetId::Concrete(MultiLocation::parent());
+		assert_noop!(
+			XcmHelper::whitelist_token(RuntimeOrigin::root(), token),
+			Error::<Test>::WhitelistedTokensLimitReached
+		);
+	});
+}
+
+#[test]
+fn test_execute_deposits_rejects_batch_exceeding_pending_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let queued: Vec<Withdraw> = (0..4u8)
+			.map(|i| {
+				let mut withdrawal = get_failed_withdrawal();
+				withdrawal.id = vec![i; 32];
+				withdrawal
+			})
+			.collect();
+		XcmHelper::execute_deposits(1, queued.encode());
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001).len(), 4);
+		let mut first_withdrawal = get_failed_withdrawal();
+		first_withdrawal.id = vec![10; 32];
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![11; 32];
+		XcmHelper::execute_deposits(
+			1,
+			vec![first_withdrawal.clone(), sec_withdrawal.clone()].encode(),
+		);
+		// Whole batch is rejected, even though one withdrawal would fit
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001), pending(queued));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&first_withdrawal.id)), None);
+		System::assert_has_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalsRejected {
+			ids: vec![first_withdrawal.id.clone(), sec_withdrawal.id.clone()],
+			reason: Error::<Test>::PendingWithdrawalsLimitReached.into(),
+		}));
+		// Withdrawals have no recipient to be refunded to
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::RejectedWithdrawalsKept {
+			ids: vec![first_withdrawal.id, sec_withdrawal.id],
+		}));
+	});
+}
+
+#[test]
+fn test_execute_deposits_rejects_batch_with_duplicate_withdrawal_id() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let queued = get_failed_withdrawal();
+		XcmHelper::execute_deposits(1, vec![queued.clone()].encode());
+		let mut failed = get_failed_withdrawal();
+		failed.id = vec![2; 32];
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			1,
+			failed.clone(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		let mut new_withdrawal = get_failed_withdrawal();
+		new_withdrawal.id = vec![3; 32];
+		for batch in [
+			vec![new_withdrawal.clone(), queued.clone()],
+			vec![new_withdrawal.clone(), failed],
+			vec![new_withdrawal.clone(), new_withdrawal.clone()],
+		] {
+			XcmHelper::execute_deposits(1, batch.encode());
+			let ids: Vec<Vec<u8>> = batch.iter().map(|withdrawal| withdrawal.id.clone()).collect();
+			System::assert_has_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalsRejected {
+				ids: ids.clone(),
+				reason: Error::<Test>::DuplicateWithdrawalId.into(),
+			}));
+			// Replayed withdrawals are never refunded automatically
+			System::assert_last_event(RuntimeEvent::XcmHelper(
+				crate::Event::RejectedWithdrawalsKept { ids },
+			));
+		}
+		// Whole batches are rejected, including the withdrawal which isn't a duplicate
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001), pending(vec![queued]));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&new_withdrawal.id)), None);
+	});
+}
+
+#[test]
+fn test_execute_deposits_rejects_batch_with_oversized_withdrawal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let first_withdrawal = get_failed_withdrawal();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 65];
+		XcmHelper::execute_deposits(
+			1,
+			vec![first_withdrawal.clone(), sec_withdrawal.clone()].encode(),
+		);
+		assert!(<PendingWithdrawals<Test>>::get(1001).is_empty());
+		System::assert_has_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalsRejected {
+			ids: vec![first_withdrawal.id.clone(), sec_withdrawal.id.clone()],
+			reason: Error::<Test>::WithdrawalTooLarge.into(),
+		}));
+		// Withdrawals have no recipient to be refunded to
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::RejectedWithdrawalsKept {
+			ids: vec![first_withdrawal.id, sec_withdrawal.id],
+		}));
+	});
+}
+
+#[test]
+fn test_execute_deposits_refunds_rejected_withdrawals_with_known_recipient() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1u8; 32] }),
+		));
+		let mut first_withdrawal = get_failed_withdrawal();
+		first_withdrawal.destination = destination.encode();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 65];
+		XcmHelper::execute_deposits(
+			1,
+			vec![first_withdrawal.clone(), sec_withdrawal.clone()].encode(),
+		);
+		assert!(<PendingWithdrawals<Test>>::get(1001).is_empty());
+		System::assert_has_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalsRejected {
+			ids: vec![first_withdrawal.id.clone(), sec_withdrawal.id],
+			reason: Error::<Test>::WithdrawalTooLarge.into(),
+		}));
+		System::assert_has_event(RuntimeEvent::XcmHelper(
+			crate::Event::RejectedWithdrawalsRefunded { ids: vec![first_withdrawal.id] },
+		));
+		// Second withdrawal has no recipient to be refunded to, so it's kept for governance
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::RejectedWithdrawalsKept {
+			ids: vec![sec_withdrawal.id.clone()],
+		}));
+		let key = sp_io::hashing::blake2_256(&sec_withdrawal.id);
+		assert_eq!(<RejectedWithdrawals<Test>>::get(key), vec![sec_withdrawal.clone()]);
+		assert_ok!(XcmHelper::refund_rejected_withdrawal(
+			RuntimeOrigin::root(),
+			sec_withdrawal.id.clone(),
+			1
+		));
+		assert!(<RejectedWithdrawals<Test>>::get(key).is_empty());
+		System::assert_last_event(RuntimeEvent::XcmHelper(
+			crate::Event::RejectedWithdrawalRefunded {
+				id: sec_withdrawal.id.clone(),
+				recipient: 1,
+			},
+		));
+		assert_noop!(
+			XcmHelper::refund_rejected_withdrawal(RuntimeOrigin::root(), sec_withdrawal.id, 1),
+			Error::<Test>::RejectedWithdrawalNotFound
+		);
+	});
+}
+
+#[test]
+fn test_execute_deposits_keeps_rejected_withdrawals_when_refund_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1u8; 32] }),
+		));
+		let mut first_withdrawal = get_failed_withdrawal();
+		first_withdrawal.destination = destination.encode();
+		let mut sec_withdrawal = first_withdrawal.clone();
+		sec_withdrawal.id = vec![2; 65];
+		ExecutorFails::set(true);
+		XcmHelper::execute_deposits(
+			1,
+			vec![first_withdrawal.clone(), sec_withdrawal.clone()].encode(),
+		);
+		ExecutorFails::set(false);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::RejectedWithdrawalsKept {
+			ids: vec![first_withdrawal.id.clone(), sec_withdrawal.id.clone()],
+		}));
+		for withdrawal in [first_withdrawal, sec_withdrawal] {
+			let key = sp_io::hashing::blake2_256(&withdrawal.id);
+			assert_eq!(<RejectedWithdrawals<Test>>::get(key), vec![withdrawal.clone()]);
+			assert_ok!(XcmHelper::refund_rejected_withdrawal(
+				RuntimeOrigin::root(),
+				withdrawal.id,
+				1
+			));
+			assert!(<RejectedWithdrawals<Test>>::get(key).is_empty());
+		}
+	});
+}
+
+#[test]
+fn test_on_initialize_carries_over_withdrawals_to_first_block_with_capacity() {
+	new_test_ext().execute_with(|| {
+		let withdrawals: Vec<Withdraw> = (0..3u8)
+			.map(|i| {
+				let mut withdrawal = get_failed_withdrawal();
+				withdrawal.id = vec![i; 32];
+				withdrawal
+			})
+			.collect();
+		<PendingWithdrawals<Test>>::insert(1, pending(withdrawals));
+		<PendingWithdrawals<Test>>::insert(2, pending(vec![get_failed_withdrawal(); 5]));
+		XcmHelper::on_initialize(1);
+		// Block 2 is full, so the withdrawal which didn't fit is moved to block 3
+		assert_eq!(<PendingWithdrawals<Test>>::get(2).len(), 5);
+		assert_eq!(<PendingWithdrawals<Test>>::get(3).len(), 1);
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&[0; 32])), Some(3));
+	});
+}
+
+#[test]
+fn test_retry_failed_withdrawal_into_full_block_returns_limit_reached() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		assert_ok!(XcmHelper::insert_failed_withdrawal(
+			5,
+			get_failed_withdrawal(),
+			WithdrawalFailureReason::BlockedByCouncil,
+		));
+		<PendingWithdrawals<Test>>::insert(11, pending(vec![get_failed_withdrawal(); 5]));
+		assert_noop!(
+			XcmHelper::retry_failed_withdrawal(RuntimeOrigin::root(), vec![1; 32]),
+			Error::<Test>::PendingWithdrawalsLimitReached
+		);
+	});
+}
+
+#[test]
+fn test_set_asset_withdrawal_config_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let config = AssetWithdrawalConfig {
+			delay: 10,
+			large_withdrawal_threshold: Some(100),
+			large_withdrawal_delay: Some(50),
+		};
+		assert_ok!(XcmHelper::set_asset_withdrawal_config(RuntimeOrigin::root(), 1, Some(config)));
+		assert_eq!(XcmHelper::get_asset_withdrawal_config(1), Some(config));
+		System::assert_last_event(RuntimeEvent::XcmHelper(
+			crate::Event::AssetWithdrawalConfigUpdated { asset_id: 1, config: Some(config) },
+		));
+		assert_noop!(
+			XcmHelper::set_asset_withdrawal_config(RuntimeOrigin::signed(1), 1, None),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn test_set_asset_withdrawal_config_with_zero_delay_returns_error() {
+	new_test_ext().execute_with(|| {
+		let config = AssetWithdrawalConfig {
+			delay: 0,
+			large_withdrawal_threshold: Some(100),
+			large_withdrawal_delay: Some(50),
+		};
+		assert_noop!(
+			XcmHelper::set_asset_withdrawal_config(RuntimeOrigin::root(), 1, Some(config)),
+			Error::<Test>::InvalidWithdrawalDelay
+		);
+		let config = AssetWithdrawalConfig { delay: 10, large_withdrawal_delay: Some(0), ..config };
+		assert_noop!(
+			XcmHelper::set_asset_withdrawal_config(RuntimeOrigin::root(), 1, Some(config)),
+			Error::<Test>::InvalidWithdrawalDelay
+		);
+		// Zero delay stored before the check is applied from the next block
+		<AssetWithdrawalConfigs<Test>>::insert(1, AssetWithdrawalConfig { delay: 0, ..config });
+		assert_eq!(XcmHelper::get_withdrawal_execution_block(5, 1, 1), Some(6));
+	});
+}
+
+#[test]
+fn test_execute_deposits_applies_asset_withdrawal_delays() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let config = AssetWithdrawalConfig {
+			delay: 10,
+			large_withdrawal_threshold: Some(100),
+			large_withdrawal_delay: Some(50),
+		};
+		<AssetWithdrawalConfigs<Test>>::insert(1, config);
+		let mut small_withdrawal = get_failed_withdrawal();
+		small_withdrawal.amount = 100;
+		let mut large_withdrawal = get_failed_withdrawal();
+		large_withdrawal.id = vec![2; 32];
+		large_withdrawal.amount = 101;
+		let mut other_asset_withdrawal = get_failed_withdrawal();
+		other_asset_withdrawal.id = vec![3; 32];
+		other_asset_withdrawal.asset_id = 2;
+		XcmHelper::execute_deposits(
+			1,
+			vec![
+				small_withdrawal.clone(),
+				large_withdrawal.clone(),
+				other_asset_withdrawal.clone(),
+			]
+			.encode(),
+		);
+		assert_eq!(<PendingWithdrawals<Test>>::get(11), pending(vec![small_withdrawal]));
+		assert_eq!(<PendingWithdrawals<Test>>::get(51), pending(vec![large_withdrawal]));
+		// Asset without config uses default delay
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001), pending(vec![other_asset_withdrawal]));
+	});
+}
+
+#[test]
+fn test_execute_deposits_holds_large_withdrawal_until_released() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let config = AssetWithdrawalConfig {
+			delay: 10,
+			large_withdrawal_threshold: Some(100),
+			large_withdrawal_delay: None,
+		};
+		<AssetWithdrawalConfigs<Test>>::insert(1, config);
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.amount = 101;
+		XcmHelper::execute_deposits(1, vec![withdrawal.clone()].encode());
+		assert!(<PendingWithdrawals<Test>>::get(11).is_empty());
+		assert_eq!(
+			XcmHelper::get_held_withdrawal(withdrawal_id(&withdrawal.id)),
+			Some(bounded(withdrawal.clone()))
+		);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalHeld {
+			id: withdrawal.id.clone(),
+		}));
+		assert_ok!(XcmHelper::do_release_held_withdrawal(&withdrawal.id));
+		assert_eq!(XcmHelper::get_held_withdrawal(withdrawal_id(&withdrawal.id)), None);
+		assert_eq!(<PendingWithdrawals<Test>>::get(2), pending(vec![withdrawal.clone()]));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&withdrawal.id)), Some(2));
+		assert_noop!(
+			XcmHelper::do_release_held_withdrawal(&withdrawal.id),
+			Error::<Test>::WithdrawalNotHeld
+		);
+	});
+}
+
+#[test]
+fn test_set_asset_rate_limit_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let limit = AssetRateLimit { window: 10, max_amount: 100 };
+		assert_ok!(XcmHelper::set_asset_rate_limit(RuntimeOrigin::root(), 1, Some(limit)));
+		assert_eq!(XcmHelper::get_asset_rate_limit(1), Some(limit));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetRateLimitUpdated {
+			asset_id: 1,
+			limit: Some(limit),
+		}));
+		assert_noop!(
+			XcmHelper::set_asset_rate_limit(RuntimeOrigin::signed(1), 1, None),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn test_on_initialize_defers_withdrawals_exceeding_rate_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		<AssetRateLimits<Test>>::insert(1, AssetRateLimit { window: 10, max_amount: 100 });
+		<WithdrawalUsages<Test>>::insert(1, WithdrawalUsage { window_start: 1, withdrawn: 60 });
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.amount = 50;
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		assert!(<FailedWithdrawals<Test>>::get(1).is_empty());
+		assert_eq!(<PendingWithdrawals<Test>>::get(11), pending(vec![withdrawal.clone()]));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&withdrawal.id)), Some(11));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalDeferred {
+			id: withdrawal.id.clone(),
+			execute_at: 11,
+			reason: WithdrawalDeferralReason::RateLimited,
+		}));
+		// Window has ended, so withdrawal is executed (and fails on the invalid destination)
+		System::set_block_number(11);
+		XcmHelper::on_initialize(11);
+		assert_eq!(<FailedWithdrawals<Test>>::get(11).len(), 1);
+	});
+}
+
+#[test]
+fn test_executed_withdrawal_starts_new_rate_limit_window() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(20);
+		let asset_id = 1;
+		assert_ok!(<Assets as Create<u64>>::create(
+			asset_id,
+			XcmHelper::get_pallet_account(),
+			true,
+			1
+		));
+		<AssetRateLimits<Test>>::insert(asset_id, AssetRateLimit { window: 10, max_amount: 100 });
+		<WithdrawalUsages<Test>>::insert(
+			asset_id,
+			WithdrawalUsage { window_start: 1, withdrawn: 100 },
+		);
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		));
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.amount = 40;
+		withdrawal.destination = destination.encode();
+		<PendingWithdrawals<Test>>::insert(20, pending(vec![withdrawal]));
+		XcmHelper::on_initialize(20);
+		assert_eq!(
+			XcmHelper::get_withdrawal_usage(asset_id),
+			Some(WithdrawalUsage { window_start: 20, withdrawn: 40 })
+		);
+	});
+}
+
+#[test]
+fn test_on_initialize_fails_withdrawal_above_rate_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		<AssetRateLimits<Test>>::insert(1, AssetRateLimit { window: 10, max_amount: 100 });
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.amount = 101;
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal]));
+		XcmHelper::on_initialize(1);
+		let failed_withdrawals = <FailedWithdrawals<Test>>::get(1);
+		assert_eq!(failed_withdrawals.len(), 1);
+		assert_eq!(failed_withdrawals[0].reason, WithdrawalFailureReason::ExceedsRateLimit);
+	});
+}
+
+#[test]
+fn test_on_initialize_fails_withdrawal_above_rate_limit_without_deferring() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		<AssetRateLimits<Test>>::insert(1, AssetRateLimit { window: 10, max_amount: 100 });
+		<WithdrawalUsages<Test>>::insert(1, WithdrawalUsage { window_start: 1, withdrawn: 50 });
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.amount = 101;
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal]));
+		XcmHelper::on_initialize(1);
+		// Withdrawal is not deferred to the end of the current window
+		assert!(<PendingWithdrawals<Test>>::get(11).is_empty());
+		let failed_withdrawals = <FailedWithdrawals<Test>>::get(1);
+		assert_eq!(failed_withdrawals.len(), 1);
+		assert_eq!(failed_withdrawals[0].reason, WithdrawalFailureReason::ExceedsRateLimit);
+	});
+}
+
+#[test]
+fn test_pause_bridge_and_unpause_bridge_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(XcmHelper::pause_bridge(RuntimeOrigin::root(), BridgeDirection::Inbound));
+		assert!(XcmHelper::is_inbound_paused());
+		assert!(!XcmHelper::is_outbound_paused());
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::BridgePaused {
+			direction: BridgeDirection::Inbound,
+		}));
+		assert_noop!(
+			XcmHelper::unpause_bridge(RuntimeOrigin::signed(1), BridgeDirection::Inbound),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::unpause_bridge(RuntimeOrigin::root(), BridgeDirection::Inbound));
+		assert!(!XcmHelper::is_inbound_paused());
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::BridgeUnpaused {
+			direction: BridgeDirection::Inbound,
+		}));
+	});
+}
+
+#[test]
+fn test_deposit_asset_with_inbound_paused_returns_error() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		XcmHelper::do_pause_bridge(BridgeDirection::Inbound);
+		let asset = MultiAsset {
+			id: AssetId::Concrete(MultiLocation::parent()),
+			fun: Fungibility::Fungible(1_000_000_000_000),
+		};
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		assert_eq!(
+			XcmHelper::deposit_asset(
+				&asset,
+				&who,
+				&XcmContext { origin: None, message_id: [0; 32], topic: None }
+			),
+			Err(TrapCode::InboundPaused.into())
+		);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::DepositTrapped {
+			beneficiary: Box::new(who),
+			asset: Box::new(asset),
+			reason: TrapCode::InboundPaused,
+		}));
+	});
+}
+
+#[test]
+fn test_on_initialize_with_outbound_paused_leaves_withdrawals_in_place() {
+	new_test_ext().execute_with(|| {
+		XcmHelper::do_pause_bridge(BridgeDirection::Outbound);
+		let first_withdrawal = get_failed_withdrawal();
+		let mut sec_withdrawal = get_failed_withdrawal();
+		sec_withdrawal.id = vec![2; 32];
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![first_withdrawal.clone()]));
+		<PendingWithdrawals<Test>>::insert(3, pending(vec![sec_withdrawal.clone()]));
+		for block_no in 1..=3 {
+			XcmHelper::on_initialize(block_no);
+		}
+		assert_eq!(<PendingWithdrawals<Test>>::get(1), pending(vec![first_withdrawal]));
+		assert_eq!(<PendingWithdrawals<Test>>::get(3), pending(vec![sec_withdrawal]));
+		assert_eq!(XcmHelper::get_withdrawal_backlog_start(), Some(1));
+		assert_ok!(XcmHelper::unpause_bridge(RuntimeOrigin::root(), BridgeDirection::Outbound));
+		// Backlog is drained before withdrawals of the current block
+		XcmHelper::on_initialize(4);
+		assert!(<PendingWithdrawals<Test>>::get(1).is_empty());
+		assert!(<PendingWithdrawals<Test>>::get(3).is_empty());
+		assert_eq!(<FailedWithdrawals<Test>>::get(4).len(), 2);
+		assert_eq!(XcmHelper::get_withdrawal_backlog_start(), None);
+	});
+}
+
+#[test]
+fn test_on_initialize_drains_withdrawal_backlog_within_weight_limit() {
+	new_test_ext().execute_with(|| {
+		XcmHelper::do_pause_bridge(BridgeDirection::Outbound);
+		let withdrawals: Vec<Withdraw> = (0..3u8)
+			.map(|i| {
+				let mut withdrawal = get_failed_withdrawal();
+				withdrawal.id = vec![i; 32];
+				withdrawal
+			})
+			.collect();
+		<PendingWithdrawals<Test>>::insert(1, pending(withdrawals.clone()));
+		XcmHelper::on_initialize(1);
+		XcmHelper::on_initialize(2);
+		assert_ok!(XcmHelper::unpause_bridge(RuntimeOrigin::root(), BridgeDirection::Outbound));
+		// Weight limit allows only two withdrawals per block, the rest stays in its block
+		XcmHelper::on_initialize(3);
+		assert_eq!(<PendingWithdrawals<Test>>::get(1), pending(withdrawals[..1].to_vec()));
+		assert_eq!(<FailedWithdrawals<Test>>::get(3).len(), 2);
+		assert_eq!(XcmHelper::get_withdrawal_backlog_start(), Some(1));
+		XcmHelper::on_initialize(4);
+		assert!(<PendingWithdrawals<Test>>::get(1).is_empty());
+		assert_eq!(<FailedWithdrawals<Test>>::get(4).len(), 1);
+		assert_eq!(XcmHelper::get_withdrawal_backlog_start(), None);
+	});
+}
+
+#[test]
+fn test_freeze_asset_and_unfreeze_asset_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_ok!(XcmHelper::freeze_asset(RuntimeOrigin::root(), asset_id));
+		assert!(XcmHelper::is_asset_frozen(asset_id));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetFrozen { asset_id }));
+		assert_noop!(
+			XcmHelper::unfreeze_asset(RuntimeOrigin::signed(1), asset_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::unfreeze_asset(RuntimeOrigin::root(), asset_id));
+		assert!(!XcmHelper::is_asset_frozen(asset_id));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetUnfrozen {
+			asset_id,
+		}));
+	});
+}
+
+#[test]
+fn test_freeze_asset_with_unregistered_asset_returns_error() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset_id =
+			XcmHelper::generate_asset_id_for_parachain(AssetId::Concrete(MultiLocation::parent()));
+		assert_noop!(
+			XcmHelper::freeze_asset(RuntimeOrigin::root(), asset_id),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert!(!XcmHelper::is_asset_frozen(asset_id));
+	});
+}
+
+#[test]
+fn test_unfreeze_asset_with_asset_not_frozen_returns_error() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_noop!(
+			XcmHelper::unfreeze_asset(RuntimeOrigin::root(), asset_id),
+			Error::<Test>::AssetNotFrozen
+		);
+	});
+}
+
+#[test]
+fn test_deposit_asset_with_frozen_asset_returns_error() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset_id = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset_id));
+		assert_ok!(XcmHelper::freeze_asset(
+			RuntimeOrigin::root(),
+			XcmHelper::generate_asset_id_for_parachain(asset_id)
+		));
+		let asset = MultiAsset { id: asset_id, fun: Fungibility::Fungible(1_000_000_000_000) };
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		assert_eq!(
+			XcmHelper::deposit_asset(
+				&asset,
+				&who,
+				&XcmContext { origin: None, message_id: [0; 32], topic: None }
+			),
+			Err(TrapCode::AssetFrozen.into())
+		);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::DepositTrapped {
+			beneficiary: Box::new(who),
+			asset: Box::new(asset),
+			reason: TrapCode::AssetFrozen,
+		}));
+	});
+}
+
+#[test]
+fn test_on_initialize_defers_withdrawals_of_frozen_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		<FrozenAssets<Test>>::insert(1, ());
+		let withdrawal = get_failed_withdrawal();
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		assert!(<FailedWithdrawals<Test>>::get(1).is_empty());
+		assert_eq!(<PendingWithdrawals<Test>>::get(1001), pending(vec![withdrawal.clone()]));
+		assert_eq!(<WithdrawalIndex<Test>>::get(withdrawal_id(&withdrawal.id)), Some(1001));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::WithdrawalDeferred {
+			id: withdrawal.id,
+			execute_at: 1001,
+			reason: WithdrawalDeferralReason::AssetFrozen,
+		}));
+	});
+}
+
+#[test]
+fn test_set_asset_metadata_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pallet_account = AssetHandlerPalletId::get().into_account_truncating();
+		let _ = Balances::deposit_creating(&pallet_account, 1_000_000_000_000u128);
+		let metadata = get_asset_metadata();
+		assert_noop!(
+			XcmHelper::set_asset_metadata(RuntimeOrigin::signed(1), metadata.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::set_asset_metadata(RuntimeOrigin::root(), metadata.clone()));
+		let asset_id =
+			XcmHelper::generate_asset_id_for_parachain(AssetId::Concrete(MultiLocation::parent()));
+		assert_eq!(XcmHelper::get_asset_metadata(asset_id), Some(metadata.clone()));
+		assert!(Assets::asset_exists(asset_id));
+		assert_eq!(Assets::minimum_balance(asset_id), 100);
+		assert_eq!(Assets::name(asset_id), b"Polkadot".to_vec());
+		assert_eq!(Assets::symbol(asset_id), b"DOT".to_vec());
+		assert_eq!(Assets::decimals(asset_id), 10);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetMetadataUpdated {
+			asset_id,
+			metadata,
+		}));
+		// Registered asset is whitelisted without creating it again
+		assert_ok!(XcmHelper::whitelist_token(
+			RuntimeOrigin::root(),
+			AssetId::Concrete(MultiLocation::parent())
+		));
+	});
+}
+
+#[test]
+fn test_set_asset_metadata_with_different_min_balance_returns_error() {
+	new_test_ext().execute_with(|| {
+		let pallet_account = AssetHandlerPalletId::get().into_account_truncating();
+		let _ = Balances::deposit_creating(&pallet_account, 1_000_000_000_000u128);
+		assert_ok!(XcmHelper::whitelist_token(
+			RuntimeOrigin::root(),
+			AssetId::Concrete(MultiLocation::parent())
+		));
+		assert_noop!(
+			XcmHelper::set_asset_metadata(RuntimeOrigin::root(), get_asset_metadata()),
+			Error::<Test>::AssetMinBalanceMismatch
+		);
+	});
+}
+
+#[test]
+fn test_deposit_asset_with_unregistered_asset_returns_error_without_storing_mapping() {
+	new_test_ext().execute_with(|| {
+		let asset_id =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(5))));
+		let asset = MultiAsset { id: asset_id, fun: Fungibility::Fungible(1_000_000_000_000) };
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		assert_eq!(
+			XcmHelper::deposit_asset(
+				&asset,
+				&who,
+				&XcmContext { origin: None, message_id: [0; 32], topic: None }
+			),
+			Err(XcmError::AssetNotFound)
+		);
+		let generated_asset_id = XcmHelper::generate_asset_id_for_parachain(asset_id);
+		assert!(!<ParachainAssets<Test>>::contains_key(generated_asset_id));
+		assert_eq!(XcmHelper::get_asset_id(asset_id), None);
+		assert_eq!(
+			XcmHelper::convert_location_to_asset_id(MultiLocation::new(
+				1,
+				Junctions::X1(Junction::Parachain(5))
+			)),
+			None
+		);
+		// Whitelisting registers the mapping
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset_id));
+		assert_eq!(XcmHelper::get_asset_id(asset_id), Some(generated_asset_id));
+		assert_eq!(XcmHelper::assets_mapping(generated_asset_id), Some(asset_id));
+	});
+}
+
+#[test]
+fn test_migrate_to_v3_prunes_never_whitelisted_asset_mappings() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<XcmHelper>();
+		let whitelisted = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), whitelisted));
+		let whitelisted_id = XcmHelper::generate_asset_id_for_parachain(whitelisted);
+		let junk = AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(5))));
+		let junk_id = XcmHelper::generate_asset_id_for_parachain(junk);
+		<ParachainAssets<Test>>::insert(junk_id, junk);
+		crate::migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(<ParachainAssets<Test>>::get(whitelisted_id), Some(whitelisted));
+		assert!(!<ParachainAssets<Test>>::contains_key(junk_id));
+		assert_eq!(XcmHelper::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn test_migrate_to_v3_keeps_mappings_of_dewhitelisted_and_referenced_assets() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<XcmHelper>();
+		let asset = |para_id| {
+			let asset = AssetId::Concrete(MultiLocation::new(
+				1,
+				Junctions::X1(Junction::Parachain(para_id)),
+			));
+			(asset, XcmHelper::generate_asset_id_for_parachain(asset))
 		};
-		assert_eq!(actual_withdrawals[1], expected_withdraw);
-		assert_noop!(XcmHelper::block_by_ele(1, 4), Error::<Test>::IndexNotFound);
+		// Removed from the whitelist after it was used
+		let (dewhitelisted, dewhitelisted_id) = asset(1000);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), dewhitelisted));
+		assert_ok!(XcmHelper::remove_whitelisted_token(RuntimeOrigin::root(), dewhitelisted));
+		// Kept aside by migration to v2 as it exceeded the whitelist limit
+		let (overflow, overflow_id) = asset(1001);
+		<ParachainAssets<Test>>::insert(overflow_id, overflow);
+		<UnmigratedWhitelistedTokens<Test>>::put(vec![overflow_id]);
+		// Referenced by pending withdrawal only
+		let (pending_asset, pending_asset_id) = asset(1002);
+		<ParachainAssets<Test>>::insert(pending_asset_id, pending_asset);
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.asset_id = pending_asset_id;
+		<PendingWithdrawals<Test>>::insert(5, pending(vec![withdrawal]));
+		let (junk, junk_id) = asset(1003);
+		<ParachainAssets<Test>>::insert(junk_id, junk);
+		crate::migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(<ParachainAssets<Test>>::get(dewhitelisted_id), Some(dewhitelisted));
+		assert_eq!(<ParachainAssets<Test>>::get(overflow_id), Some(overflow));
+		assert_eq!(<ParachainAssets<Test>>::get(pending_asset_id), Some(pending_asset));
+		assert!(!<ParachainAssets<Test>>::contains_key(junk_id));
+	});
+}
+
+#[test]
+fn test_generate_asset_id_for_parachain_with_different_anchors_returns_same_id() {
+	new_test_ext().execute_with(|| {
+		let relative = AssetId::Concrete(MultiLocation::new(
+			1,
+			Junctions::X2(Junction::Parachain(1000), Junction::GeneralIndex(1)),
+		));
+		let global = AssetId::Concrete(MultiLocation::new(
+			2,
+			Junctions::X3(
+				Junction::GlobalConsensus(NetworkId::Polkadot),
+				Junction::Parachain(1000),
+				Junction::GeneralIndex(1),
+			),
+		));
+		assert_eq!(XcmHelper::canonicalise_asset(global), relative);
+		assert_eq!(
+			XcmHelper::generate_asset_id_for_parachain(global),
+			XcmHelper::generate_asset_id_for_parachain(relative)
+		);
+		// Own assets seen from the relay chain are re-anchored to this chain
+		let own_asset = AssetId::Concrete(MultiLocation::new(
+			1,
+			Junctions::X2(Junction::Parachain(2040), Junction::GeneralIndex(1)),
+		));
+		assert_eq!(
+			XcmHelper::canonicalise_asset(own_asset),
+			AssetId::Concrete(MultiLocation::new(0, Junctions::X1(Junction::GeneralIndex(1))))
+		);
+		// Native asset seen from the relay chain or by its global location
+		let native_locations = [
+			MultiLocation::new(1, Junctions::X1(Junction::Parachain(2040))),
+			MultiLocation::new(
+				2,
+				Junctions::X2(
+					Junction::GlobalConsensus(NetworkId::Polkadot),
+					Junction::Parachain(2040),
+				),
+			),
+		];
+		for location in native_locations {
+			let native = AssetId::Concrete(location);
+			assert_eq!(XcmHelper::generate_asset_id_for_parachain(native), NativeAssetId::get());
+			assert_eq!(XcmHelper::get_asset_id(native), Some(NativeAssetId::get()));
+			assert_eq!(
+				XcmHelper::convert_location_to_asset_id(location),
+				Some(NativeAssetId::get())
+			);
+		}
+		// Registered asset is found using any of its forms
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), global));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(relative);
+		assert_eq!(XcmHelper::get_asset_id(relative), Some(asset_id));
+		assert_eq!(XcmHelper::get_asset_id(global), Some(asset_id));
+		assert_eq!(XcmHelper::assets_mapping(asset_id), Some(relative));
+	});
+}
+
+#[test]
+fn test_migrate_to_v4_canonicalises_asset_mappings() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<XcmHelper>();
+		let global = AssetId::Concrete(MultiLocation::new(
+			2,
+			Junctions::X2(
+				Junction::GlobalConsensus(NetworkId::Polkadot),
+				Junction::Parachain(1000),
+			),
+		));
+		let relative =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		// Legacy id generated from non canonical form is kept
+		let legacy_asset_id = 1;
+		<ParachainAssets<Test>>::insert(legacy_asset_id, global);
+		crate::migration::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(<ParachainAssets<Test>>::get(legacy_asset_id), Some(relative));
+		assert_eq!(XcmHelper::get_asset_id(global), Some(legacy_asset_id));
+		assert_eq!(XcmHelper::get_asset_id(relative), Some(legacy_asset_id));
+		assert_eq!(XcmHelper::on_chain_storage_version(), 4);
+	});
+}
+
+#[test]
+fn test_migrate_to_v5_indexes_pending_and_failed_withdrawals() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(4).put::<XcmHelper>();
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.id = vec![2; 32];
+		let failed_withdrawal = FailedWithdrawal {
+			withdraw: bounded(withdrawal),
+			reason: WithdrawalFailureReason::Unknown,
+			attempted_at: 3,
+		};
+		<FailedWithdrawals<Test>>::insert(3, BoundedVec::truncate_from(vec![failed_withdrawal]));
+		<PendingWithdrawals<Test>>::insert(7, pending(vec![get_failed_withdrawal()]));
+		crate::migration::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(XcmHelper::get_withdrawal_block(withdrawal_id(&[1; 32])), Some(7));
+		assert_eq!(XcmHelper::get_failed_withdrawal_block(withdrawal_id(&[2; 32])), Some(3));
+		assert_eq!(XcmHelper::on_chain_storage_version(), 5);
+	});
+}
+
+#[test]
+fn test_migrate_to_v2_keeps_oversized_withdrawals_and_tokens() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<XcmHelper>();
+		let mut oversized = get_failed_withdrawal();
+		oversized.id = vec![2; 65];
+		frame_support::storage::unhashed::put(
+			&<PendingWithdrawals<Test>>::hashed_key_for(5),
+			&vec![get_failed_withdrawal(), oversized.clone()],
+		);
+		let tokens: Vec<u128> = (0..7).collect();
+		frame_support::storage::unhashed::put(&<WhitelistedTokens<Test>>::hashed_key(), &tokens);
+		crate::migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(<PendingWithdrawals<Test>>::get(5), pending(vec![get_failed_withdrawal()]));
+		assert_eq!(
+			XcmHelper::get_unmigrated_withdrawal(sp_io::hashing::blake2_256(&oversized.id)),
+			Some(oversized)
+		);
+		assert_eq!(<WhitelistedTokens<Test>>::get().into_inner(), tokens[..5].to_vec());
+		assert_eq!(XcmHelper::get_unmigrated_whitelisted_tokens(), tokens[5..].to_vec());
+		assert_eq!(XcmHelper::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn test_refund_unmigrated_withdrawal_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.id = vec![2; 65];
+		<UnmigratedWithdrawals<Test>>::insert(
+			sp_io::hashing::blake2_256(&withdrawal.id),
+			withdrawal.clone(),
+		);
+		assert_noop!(
+			XcmHelper::refund_unmigrated_withdrawal(
+				RuntimeOrigin::signed(1),
+				withdrawal.id.clone(),
+				1
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::refund_unmigrated_withdrawal(
+			RuntimeOrigin::root(),
+			withdrawal.id.clone(),
+			1
+		));
+		System::assert_last_event(RuntimeEvent::XcmHelper(
+			crate::Event::UnmigratedWithdrawalRefunded { id: withdrawal.id.clone(), recipient: 1 },
+		));
+		assert_noop!(
+			XcmHelper::refund_unmigrated_withdrawal(RuntimeOrigin::root(), withdrawal.id, 1),
+			Error::<Test>::UnmigratedWithdrawalNotFound
+		);
+	});
+}
+
+#[test]
+fn test_migrate_to_v6_moves_foreign_fees_into_fee_pot() {
+	new_test_ext().execute_with(|| {
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		StorageVersion::new(5).put::<XcmHelper>();
+		let pallet_account = XcmHelper::get_pallet_account();
+		assert_ok!(<Assets as Mutate<u64>>::mint_into(asset_id, &pallet_account, 1_000));
+		let _ = Balances::deposit_creating(&pallet_account, 1_000_000_000_000);
+		crate::migration::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &pallet_account), 0);
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &FeePotAccount::get()), 1_000);
+		// Native balance backs native tokens sent to other chains, so it stays in place
+		assert_eq!(Balances::free_balance(pallet_account), 1_000_000_000_000);
+		assert_eq!(XcmHelper::on_chain_storage_version(), 6);
+	});
+}
+
+#[test]
+fn test_asset_alias_resolves_to_existing_asset_id() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let alias_location = MultiLocation::new(
+			1,
+			Junctions::X2(Junction::Parachain(1000), Junction::PalletInstance(50)),
+		);
+		let alias = AssetId::Concrete(alias_location);
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		assert_noop!(
+			XcmHelper::add_asset_alias(RuntimeOrigin::root(), asset_id, alias),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_noop!(
+			XcmHelper::add_asset_alias(RuntimeOrigin::signed(1), asset_id, alias),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::add_asset_alias(RuntimeOrigin::root(), asset_id, alias));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetAliasAdded {
+			asset_id,
+			alias,
+		}));
+		assert_noop!(
+			XcmHelper::add_asset_alias(RuntimeOrigin::root(), asset_id, alias),
+			Error::<Test>::AssetLocationAlreadyMapped
+		);
+		assert_eq!(XcmHelper::convert_location_to_asset_id(alias_location), Some(asset_id));
+		// Outbound location stays the canonical one
+		assert_eq!(
+			XcmHelper::convert_asset_id_to_location(asset_id),
+			Some(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))))
+		);
+		// Whitelisting alias doesn't create a new asset
+		assert_noop!(
+			XcmHelper::whitelist_token(RuntimeOrigin::root(), alias),
+			Error::<Test>::TokenIsAlreadyWhitelisted
+		);
+	});
+}
+
+#[test]
+fn test_set_canonical_asset_location_and_remove_asset_alias_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000))));
+		let alias_location = MultiLocation::new(
+			1,
+			Junctions::X2(Junction::Parachain(1000), Junction::PalletInstance(50)),
+		);
+		let alias = AssetId::Concrete(alias_location);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		assert_noop!(
+			XcmHelper::set_canonical_asset_location(RuntimeOrigin::root(), asset_id, alias),
+			Error::<Test>::AssetLocationNotMapped
+		);
+		assert_ok!(XcmHelper::add_asset_alias(RuntimeOrigin::root(), asset_id, alias));
+		assert_ok!(XcmHelper::set_canonical_asset_location(RuntimeOrigin::root(), asset_id, alias));
+		System::assert_last_event(RuntimeEvent::XcmHelper(
+			crate::Event::CanonicalAssetLocationUpdated { asset_id, location: alias },
+		));
+		assert_eq!(XcmHelper::convert_asset_id_to_location(asset_id), Some(alias_location));
+		assert_noop!(
+			XcmHelper::remove_asset_alias(RuntimeOrigin::root(), alias),
+			Error::<Test>::CanonicalAssetLocation
+		);
+		// Previous canonical location is now an alias
+		assert_ok!(XcmHelper::remove_asset_alias(RuntimeOrigin::root(), asset));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetAliasRemoved {
+			asset_id,
+			alias: asset,
+		}));
+		assert_eq!(XcmHelper::get_asset_id(asset), None);
+		assert_eq!(XcmHelper::get_asset_id(alias), Some(asset_id));
+	});
+}
+
+#[test]
+fn test_abstract_asset_is_deposited_and_withdrawn_through_transact_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Abstract([1; 32]);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		assert_eq!(XcmHelper::get_asset_id(asset), Some(asset_id));
+		let multi_asset = MultiAsset { id: asset, fun: Fungibility::Fungible(1_000_000_000_000) };
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		let context =
+			XcmContext { origin: Some(MultiLocation::parent()), message_id: [7; 32], topic: None };
+		assert_ok!(XcmHelper::deposit_asset(&multi_asset, &who, &context));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetDeposited(
+			Box::new(who),
+			Box::new(multi_asset.clone()),
+			asset_id,
+			[7; 32],
+			Some(Box::new(MultiLocation::parent())),
+		)));
+		let account = AccountIdConverter::convert_ref(who).unwrap();
+		assert_ok!(<Assets as Mutate<u64>>::mint_into(asset_id, &account, 2_000_000_000_000));
+		assert_ok!(XcmHelper::withdraw_asset(&multi_asset, &who, Some(&context)));
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &account), 1_000_000_000_000);
+	});
+}
+
+#[test]
+fn test_set_abstract_asset_location_is_used_for_withdrawals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Abstract([1; 32]);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		let destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::Parachain(1000)),
+		));
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.asset_id = asset_id;
+		withdrawal.amount = 1_000_000_000_000;
+		withdrawal.destination = destination.encode();
+		// Without location withdrawal can't be sent out
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(1)[0].reason,
+			WithdrawalFailureReason::UnknownAssetMapping
+		);
+		let location = MultiLocation::new(
+			1,
+			Junctions::X2(Junction::Parachain(1000), Junction::GeneralIndex(1)),
+		);
+		assert_noop!(
+			XcmHelper::set_abstract_asset_location(
+				RuntimeOrigin::signed(1),
+				asset_id,
+				Some(location)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::set_abstract_asset_location(
+			RuntimeOrigin::root(),
+			asset_id,
+			Some(location)
+		));
+		System::assert_last_event(RuntimeEvent::XcmHelper(
+			crate::Event::AbstractAssetLocationUpdated { asset_id, location: Some(location) },
+		));
+		assert_eq!(XcmHelper::convert_asset_id_to_location(asset_id), Some(location));
+		// Mock has no xcm executor, so withdrawal now fails during xcm transfer
+		<PendingWithdrawals<Test>>::insert(2, pending(vec![withdrawal]));
+		XcmHelper::on_initialize(2);
+		assert!(matches!(
+			<FailedWithdrawals<Test>>::get(2)[0].reason,
+			WithdrawalFailureReason::XcmTransferFailed(_)
+		));
+		// Concrete assets use their own location
+		let concrete = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), concrete));
+		assert_noop!(
+			XcmHelper::set_abstract_asset_location(
+				RuntimeOrigin::root(),
+				XcmHelper::generate_asset_id_for_parachain(concrete),
+				Some(location)
+			),
+			Error::<Test>::AssetIsNotAbstract
+		);
+	});
+}
+
+#[test]
+fn test_set_asset_non_fungible_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let collection =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::GeneralIndex(1))));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(collection);
+		assert_noop!(
+			XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), collection));
+		assert_noop!(
+			XcmHelper::set_asset_non_fungible(RuntimeOrigin::signed(1), asset_id, true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true));
+		assert!(XcmHelper::is_non_fungible_asset(asset_id));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetFungibilityUpdated {
+			asset_id,
+			non_fungible: true,
+		}));
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, false));
+		assert!(!XcmHelper::is_non_fungible_asset(asset_id));
+	});
+}
+
+#[test]
+fn test_non_fungible_asset_is_deposited_as_single_instance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let collection =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::GeneralIndex(1))));
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), collection));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(collection);
+		let nft =
+			MultiAsset { id: collection, fun: Fungibility::NonFungible(AssetInstance::Index(42)) };
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+		// Instances of fungible asset are rejected
+		assert_eq!(XcmHelper::deposit_asset(&nft, &who, &context), Err(XcmError::AssetNotFound));
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true));
+		assert_ok!(XcmHelper::deposit_asset(&nft, &who, &context));
+		// Every instance is deposited under its own asset id
+		let instance_id =
+			XcmHelper::generate_non_fungible_instance_id(asset_id, AssetInstance::Index(42));
+		assert_ne!(
+			instance_id,
+			XcmHelper::generate_non_fungible_instance_id(asset_id, AssetInstance::Index(43))
+		);
+		assert_eq!(
+			XcmHelper::get_non_fungible_instance(instance_id),
+			Some((asset_id, AssetInstance::Index(42)))
+		);
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetDeposited(
+			Box::new(who),
+			Box::new(nft.clone()),
+			instance_id,
+			[0; 32],
+			None,
+		)));
+		// Instances are frozen together with their collection
+		assert_ok!(XcmHelper::freeze_asset(RuntimeOrigin::root(), asset_id));
+		assert!(XcmHelper::is_asset_frozen(instance_id));
+		// Amounts of non-fungible asset are rejected
+		let fungible = MultiAsset { id: collection, fun: Fungibility::Fungible(1) };
+		assert_eq!(
+			XcmHelper::deposit_asset(&fungible, &who, &context),
+			Err(XcmError::AssetNotFound)
+		);
+		// Only pallet account can withdraw instances
+		assert_eq!(
+			XcmHelper::withdraw_asset(&nft, &who, Some(&context)).map(|_| ()),
+			Err(XcmError::NotWithdrawable)
+		);
+		let mut id = [0; 32];
+		id[..8].copy_from_slice(&XcmHelper::get_pallet_account().encode());
+		let pallet_account =
+			MultiLocation::new(0, Junctions::X1(Junction::AccountId32 { network: None, id }));
+		assert_ok!(XcmHelper::withdraw_asset(&nft, &pallet_account, Some(&context)));
+	});
+}
+
+#[test]
+fn test_failed_non_fungible_deposit_leaves_no_instance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let collection =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::GeneralIndex(1))));
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), collection));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(collection);
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true));
+		let nft =
+			MultiAsset { id: collection, fun: Fungibility::NonFungible(AssetInstance::Index(42)) };
+		let who = MultiLocation::new(
+			0,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		);
+		let context = XcmContext { origin: None, message_id: [0; 32], topic: None };
+		ExecutorFails::set(true);
+		assert_eq!(
+			XcmHelper::deposit_asset(&nft, &who, &context),
+			Err(TrapCode::TheaDepositFailed.into())
+		);
+		ExecutorFails::set(false);
+		let instance_id =
+			XcmHelper::generate_non_fungible_instance_id(asset_id, AssetInstance::Index(42));
+		assert!(XcmHelper::get_non_fungible_instance(instance_id).is_none());
+	});
+}
+
+#[test]
+fn test_non_fungible_withdrawal_requires_single_instance_and_reserve_destination() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let collection =
+			AssetId::Concrete(MultiLocation::new(1, Junctions::X1(Junction::GeneralIndex(1))));
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), collection));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(collection);
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true));
+		let mut withdrawal = get_failed_withdrawal();
+		withdrawal.asset_id = asset_id;
+		withdrawal.amount = 1;
+		withdrawal.destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X1(Junction::AccountId32 { network: None, id: [1; 32] }),
+		))
+		.encode();
+		// Collection itself can't be withdrawn, only its instances
+		<PendingWithdrawals<Test>>::insert(1, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(1);
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(1)[0].reason,
+			WithdrawalFailureReason::InvalidNonFungibleInstance
+		);
+		// Instance is withdrawn as a single unit
+		let instance_id =
+			XcmHelper::generate_non_fungible_instance_id(asset_id, AssetInstance::Index(42));
+		<NonFungibleInstances<Test>>::insert(instance_id, (asset_id, AssetInstance::Index(42)));
+		withdrawal.asset_id = instance_id;
+		withdrawal.amount = 2;
+		<PendingWithdrawals<Test>>::insert(2, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(2);
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(2)[0].reason,
+			WithdrawalFailureReason::InvalidNonFungibleAmount
+		);
+		withdrawal.amount = 1;
+		// Destination is not on the reserve chain of collection
+		let relay_destination = withdrawal.destination.clone();
+		withdrawal.destination = VersionedMultiLocation::V3(MultiLocation::new(
+			1,
+			Junctions::X2(
+				Junction::Parachain(1000),
+				Junction::AccountId32 { network: None, id: [1; 32] },
+			),
+		))
+		.encode();
+		<PendingWithdrawals<Test>>::insert(3, pending(vec![withdrawal.clone()]));
+		XcmHelper::on_initialize(3);
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(3)[0].reason,
+			WithdrawalFailureReason::UnsupportedNonFungibleDestination
+		);
+		// Mock has no xcm executor, so withdrawal fails while executing the message
+		withdrawal.destination = relay_destination;
+		<PendingWithdrawals<Test>>::insert(4, pending(vec![withdrawal]));
+		XcmHelper::on_initialize(4);
+		assert_eq!(
+			<FailedWithdrawals<Test>>::get(4)[0].reason,
+			WithdrawalFailureReason::XcmExecutionFailed(XcmError::Unimplemented)
+		);
+		// Instance is kept until it leaves this chain
+		assert!(XcmHelper::get_non_fungible_instance(instance_id).is_some());
+	});
+}
+
+#[test]
+fn test_set_asset_price_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		let price = FixedU128::from_rational(1, 10);
+		assert_noop!(
+			XcmHelper::set_asset_price(RuntimeOrigin::root(), asset_id, Some(price)),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_noop!(
+			XcmHelper::set_asset_price(RuntimeOrigin::signed(1), asset_id, Some(price)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::set_asset_price(RuntimeOrigin::root(), asset_id, Some(price)));
+		assert_eq!(XcmHelper::get_native_token_price(asset_id), Some(price));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetPriceUpdated {
+			asset_id,
+			price: Some(price),
+		}));
+		assert_ok!(XcmHelper::set_asset_price(RuntimeOrigin::root(), asset_id, None));
+		assert_eq!(XcmHelper::get_native_token_price(asset_id), None);
+	});
+}
+
+#[test]
+fn test_set_asset_fee_rate_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		let rate = AssetFeeRate { units_per_second: 1_000_000_000_000, units_per_pov_byte: 10 };
+		assert_noop!(
+			XcmHelper::set_asset_fee_rate(RuntimeOrigin::root(), asset_id, Some(rate)),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_noop!(
+			XcmHelper::set_asset_fee_rate(RuntimeOrigin::signed(1), asset_id, Some(rate)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmHelper::set_asset_fee_rate(RuntimeOrigin::root(), asset_id, Some(rate)));
+		assert_eq!(XcmHelper::get_fee_rate(asset_id), Some(rate));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::AssetFeeRateUpdated {
+			asset_id,
+			rate: Some(rate),
+		}));
+		// Half a second of execution and 100 bytes of proof
+		assert_eq!(rate.fee(Weight::from_parts(500_000_000_000, 100)), 500_000_000_000 + 1_000);
+		assert_ok!(XcmHelper::set_asset_fee_rate(RuntimeOrigin::root(), asset_id, None));
+		assert_eq!(XcmHelper::get_fee_rate(asset_id), None);
+	});
+}
+
+#[test]
+fn test_swap_into_native_fee_returns_ok() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset = AssetId::Concrete(MultiLocation::parent());
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(asset);
+		let fee_pot = FeePotAccount::get();
+		let _ = Balances::deposit_creating(&fee_pot, TOKEN);
+		assert_eq!(XcmHelper::quote_native_fee(asset_id, 1_000), None);
+		assert_noop!(
+			XcmHelper::swap_into_native_fee(asset_id, 2_100, 1_000),
+			DispatchError::Other("No pool")
+		);
+		MockSwap::create_pool(asset_id, NativeAssetId::get(), 2_000_000, 1_000_000);
+		// 2_000_000 * 1_000 / (1_000_000 - 1_000) rounded up
+		assert_eq!(XcmHelper::quote_native_fee(asset_id, 1_000), Some(2_003));
+		// Minted asset is rolled back if the swap fails
+		assert_noop!(
+			XcmHelper::swap_into_native_fee(asset_id, 2_002, 1_000),
+			DispatchError::Other("Slippage exceeded")
+		);
+		assert_ok!(XcmHelper::swap_into_native_fee(asset_id, 2_100, 1_000));
+		System::assert_last_event(RuntimeEvent::XcmHelper(crate::Event::XcmFeeSwapped {
+			asset_id,
+			amount_in: 2_003,
+			fee: 1_000,
+		}));
+		assert_eq!(Balances::free_balance(fee_pot), TOKEN + 1_000);
+		assert_eq!(<Assets as Inspect<u64>>::balance(asset_id, &fee_pot), 97);
+		assert_eq!(
+			MockSwap::get_reserves(asset_id, NativeAssetId::get()),
+			Some((2_002_003, 999_000))
+		);
 	});
 }
 
@@ -173,3 +1957,38 @@ fn real_test_vector() {
 		assert_ok!(TheaMessageHandler::incoming_message(RuntimeOrigin::none(), message, vec![(0, signature.into())]));
 	})
 }
+
+fn get_failed_withdrawal() -> Withdraw {
+	Withdraw {
+		id: vec![1; 32],
+		asset_id: 1,
+		amount: 1,
+		destination: vec![],
+		is_blocked: false,
+		extra: vec![],
+	}
+}
+
+fn bounded(withdrawal: Withdraw) -> BoundedWithdraw {
+	BoundedWithdraw::try_from(withdrawal).unwrap()
+}
+
+fn pending(
+	withdrawals: Vec<Withdraw>,
+) -> BoundedVec<BoundedWithdraw, MaxPendingWithdrawalsPerBlock> {
+	BoundedVec::try_from(withdrawals.into_iter().map(bounded).collect::<Vec<_>>()).unwrap()
+}
+
+fn withdrawal_id(id: &[u8]) -> WithdrawalId {
+	WithdrawalId::try_from(id.to_vec()).unwrap()
+}
+
+fn get_asset_metadata() -> AssetMetadata {
+	AssetMetadata {
+		name: BoundedVec::truncate_from(b"Polkadot".to_vec()),
+		symbol: BoundedVec::truncate_from(b"DOT".to_vec()),
+		decimals: 10,
+		min_balance: 100,
+		location: MultiLocation::parent(),
+	}
+}
@@ -0,0 +1,638 @@
+// This file is part of Polkadex.
+
+// Copyright (C) 2020-2023 Polkadex oü.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+//! XCM weight traders charging execution in foreign assets, shared by the runtime and the XCM
+//! simulator.
+
+use crate::{
+	AssetFeeRate, AssetFeeRateProvider, AssetIdConverter, AssetPriceProvider, NativeFeeSwapper,
+	WhitelistedTokenHandler,
+};
+use frame_support::{log, weights::WeightToFee as WeightToFeeT};
+use sp_runtime::{FixedPointNumber, FixedU128};
+use sp_std::marker::PhantomData;
+use xcm::latest::{prelude::*, Weight};
+use xcm_builder::TakeRevenue;
+use xcm_executor::{traits::WeightTrader, Assets};
+
+/// Returns location of the fungible asset used to pay for XCM execution. Payment holding several
+/// assets is rejected, as traders charge all weight in a single asset.
+fn payment_asset_location(payment: &Assets) -> Result<MultiLocation, XcmError> {
+	let mut fungible_assets = payment.fungible_assets_iter();
+	let payment_asset = fungible_assets.next().ok_or(XcmError::NotHoldingFees)?;
+	if fungible_assets.next().is_some() {
+		return Err(XcmError::TooExpensive)
+	}
+	match payment_asset.id {
+		AssetId::Concrete(location) => Ok(location),
+		AssetId::Abstract(_) => Err(XcmError::AssetNotFound),
+	}
+}
+
+/// Conversion of fee in native token into the asset used for payment
+#[derive(Clone, Copy)]
+enum FeeConversion {
+	/// Asset is swapped into native token using AMM pool
+	Swap(u128),
+	/// Asset is charged using price of native token set by governance
+	Price(FixedU128),
+}
+
+/// Charges XCM execution in foreign asset, either swapped into native token through AMM pool or
+/// priced by governance.
+pub struct ForeignAssetFeeHandler<T, R, AC, WH, P, S>
+where
+	T: WeightToFeeT<Balance = u128>,
+	R: TakeRevenue,
+	AC: AssetIdConverter,
+	WH: WhitelistedTokenHandler,
+	P: AssetPriceProvider,
+	S: NativeFeeSwapper,
+{
+	/// Total used weight
+	weight: Weight,
+	/// Total consumed assets
+	consumed: u128,
+	/// Total fee in native token
+	fee_in_native_token: u128,
+	/// Asset Id (as MultiLocation) of the asset used for payment and its conversion
+	asset_location_and_conversion: Option<(MultiLocation, FeeConversion)>,
+	_pd: PhantomData<(T, R, AC, WH, P, S)>,
+}
+
+impl<T, R, AC, WH, P, S> WeightTrader for ForeignAssetFeeHandler<T, R, AC, WH, P, S>
+where
+	T: WeightToFeeT<Balance = u128>,
+	R: TakeRevenue,
+	AC: AssetIdConverter,
+	WH: WhitelistedTokenHandler,
+	P: AssetPriceProvider,
+	S: NativeFeeSwapper,
+{
+	fn new() -> Self {
+		Self {
+			weight: Weight::zero(),
+			consumed: 0,
+			fee_in_native_token: 0,
+			asset_location_and_conversion: None,
+			_pd: PhantomData,
+		}
+	}
+
+	/// Charges fee in foreign asset. Assets having AMM pool with native token are charged amount
+	/// quoted by the pool and swapped into native token once execution ends, other assets have
+	/// to be whitelisted and are charged using price of native token in the asset. All weight
+	/// bought by the trader has to be paid in the same asset.
+	/// Fails with `NotHoldingFees` if payment has no fungible asset, `AssetNotFound` if payment
+	/// asset is not registered and `TooExpensive` if payment holds several assets or an asset
+	/// different from the one already used, if the asset has no pool and is not whitelisted or
+	/// has no price or if payment doesn't cover the fee.
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: Assets,
+	) -> sp_std::result::Result<Assets, XcmError> {
+		let fee_in_native_token = T::weight_to_fee(&weight);
+		let location = payment_asset_location(&payment)?;
+		let foreign_currency_asset_id =
+			AC::convert_location_to_asset_id(location).ok_or(XcmError::AssetNotFound)?;
+		if let Some((old_asset_location, _)) = self.asset_location_and_conversion {
+			if old_asset_location != location {
+				return Err(XcmError::TooExpensive)
+			}
+		}
+		let total_fee_in_native_token =
+			self.fee_in_native_token.saturating_add(fee_in_native_token);
+		// Whole fee is quoted, so the swap at the end of execution is covered by consumed assets
+		let (conversion, expected_fee_in_foreign_currency) =
+			match S::quote_native_fee(foreign_currency_asset_id, total_fee_in_native_token) {
+				Some(total_fee) => (
+					FeeConversion::Swap(foreign_currency_asset_id),
+					total_fee.saturating_sub(self.consumed),
+				),
+				None => {
+					if !WH::check_whitelisted_token(foreign_currency_asset_id) {
+						return Err(XcmError::TooExpensive)
+					}
+					let price = P::get_native_token_price(foreign_currency_asset_id)
+						.ok_or(XcmError::TooExpensive)?;
+					(FeeConversion::Price(price), price.saturating_mul_int(fee_in_native_token))
+				},
+			};
+		let unused = payment
+			.checked_sub((location, expected_fee_in_foreign_currency).into())
+			.map_err(|_| XcmError::TooExpensive)?;
+		self.weight = self.weight.saturating_add(weight);
+		self.consumed = self.consumed.saturating_add(expected_fee_in_foreign_currency);
+		self.fee_in_native_token = total_fee_in_native_token;
+		self.asset_location_and_conversion = Some((location, conversion));
+		Ok(unused)
+	}
+
+	/// Refunds unused weight at the price it was bought for, assets swapped through AMM pool are
+	/// refunded above the quote of the remaining fee.
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		let (location, conversion) = self.asset_location_and_conversion?;
+		let weight = weight.min(self.weight);
+		let fee_in_native_token = T::weight_to_fee(&weight).min(self.fee_in_native_token);
+		let remaining_fee_in_native_token =
+			self.fee_in_native_token.saturating_sub(fee_in_native_token);
+		let refund = match conversion {
+			FeeConversion::Swap(_) if remaining_fee_in_native_token == 0 => self.consumed,
+			FeeConversion::Swap(asset_id) => self.consumed.saturating_sub(
+				S::quote_native_fee(asset_id, remaining_fee_in_native_token)
+					.unwrap_or(self.consumed),
+			),
+			FeeConversion::Price(price) => price.saturating_mul_int(fee_in_native_token),
+		}
+		.min(self.consumed);
+		self.weight = self.weight.saturating_sub(weight);
+		self.consumed = self.consumed.saturating_sub(refund);
+		self.fee_in_native_token = remaining_fee_in_native_token;
+		if refund > 0 {
+			Some((location, refund).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<T, R, AC, WH, P, S> Drop for ForeignAssetFeeHandler<T, R, AC, WH, P, S>
+where
+	T: WeightToFeeT<Balance = u128>,
+	R: TakeRevenue,
+	AC: AssetIdConverter,
+	WH: WhitelistedTokenHandler,
+	P: AssetPriceProvider,
+	S: NativeFeeSwapper,
+{
+	fn drop(&mut self) {
+		if let Some((asset_location, conversion)) = self.asset_location_and_conversion {
+			if self.consumed == 0 {
+				return
+			}
+			if let FeeConversion::Swap(asset_id) = conversion {
+				match S::swap_into_native_fee(asset_id, self.consumed, self.fee_in_native_token) {
+					Ok(()) => return,
+					Err(error) => {
+						log::error!(target: "xcm-trader", "Failed to swap XCM fee: {:?}", error)
+					},
+				}
+			}
+			R::take_revenue((asset_location, self.consumed).into());
+		}
+	}
+}
+
+/// Charges XCM execution in foreign asset using fixed fee rate of the asset, set by governance.
+/// Used for assets like DOT and USDT which need deterministic fees.
+pub struct FixedRateOfForeignAsset<AC, F, R>
+where
+	AC: AssetIdConverter,
+	F: AssetFeeRateProvider,
+	R: TakeRevenue,
+{
+	/// Total used weight
+	weight: Weight,
+	/// Total consumed assets
+	consumed: u128,
+	/// Asset Id (as MultiLocation) and fee rate of the asset used for payment
+	asset_location_and_rate: Option<(MultiLocation, AssetFeeRate)>,
+	_pd: PhantomData<(AC, F, R)>,
+}
+
+impl<AC, F, R> WeightTrader for FixedRateOfForeignAsset<AC, F, R>
+where
+	AC: AssetIdConverter,
+	F: AssetFeeRateProvider,
+	R: TakeRevenue,
+{
+	fn new() -> Self {
+		Self {
+			weight: Weight::zero(),
+			consumed: 0,
+			asset_location_and_rate: None,
+			_pd: PhantomData,
+		}
+	}
+
+	/// Fails with `NotHoldingFees` if payment has no fungible asset, `AssetNotFound` if payment
+	/// asset is not registered and `TooExpensive` if payment holds several assets or an asset
+	/// different from the one already used, if the asset has no fee rate or a zero one, which
+	/// would make execution free, or if payment doesn't cover the fee.
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: Assets,
+	) -> sp_std::result::Result<Assets, XcmError> {
+		let location = payment_asset_location(&payment)?;
+		let asset_id = AC::convert_location_to_asset_id(location).ok_or(XcmError::AssetNotFound)?;
+		let rate = F::get_fee_rate(asset_id)
+			.filter(|rate| rate.units_per_second > 0 || rate.units_per_pov_byte > 0)
+			.ok_or(XcmError::TooExpensive)?;
+		if let Some((old_asset_location, _)) = self.asset_location_and_rate {
+			if old_asset_location != location {
+				return Err(XcmError::TooExpensive)
+			}
+		}
+		let fee = rate.fee(weight);
+		let unused = payment
+			.checked_sub((location, fee).into())
+			.map_err(|_| XcmError::TooExpensive)?;
+		self.weight = self.weight.saturating_add(weight);
+		self.consumed = self.consumed.saturating_add(fee);
+		self.asset_location_and_rate = Some((location, rate));
+		Ok(unused)
+	}
+
+	/// Refunds unused weight at the rate it was bought for.
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		let (location, rate) = self.asset_location_and_rate?;
+		let weight = weight.min(self.weight);
+		let refund = rate.fee(weight).min(self.consumed);
+		self.weight = self.weight.saturating_sub(weight);
+		self.consumed = self.consumed.saturating_sub(refund);
+		if refund > 0 {
+			Some((location, refund).into())
+		} else {
+			None
+		}
+	}
+}
+
+impl<AC, F, R> Drop for FixedRateOfForeignAsset<AC, F, R>
+where
+	AC: AssetIdConverter,
+	F: AssetFeeRateProvider,
+	R: TakeRevenue,
+{
+	fn drop(&mut self) {
+		if let Some((asset_location, _)) = self.asset_location_and_rate {
+			if self.consumed > 0 {
+				R::take_revenue((asset_location, self.consumed).into());
+			}
+		}
+	}
+}
+
+/// Wraps tuple of traders so all weight bought during execution is paid in a single asset, even
+/// if different assets would be accepted by different traders of the tuple.
+pub struct SingleFeeAsset<Traders: WeightTrader> {
+	/// Traders charging the execution
+	traders: Traders,
+	/// Asset used by the first successful payment
+	fee_asset: Option<AssetId>,
+}
+
+impl<Traders: WeightTrader> WeightTrader for SingleFeeAsset<Traders> {
+	fn new() -> Self {
+		Self { traders: Traders::new(), fee_asset: None }
+	}
+
+	/// Fails with `TooExpensive` if payment holds an asset different from the one used by
+	/// previous payments, otherwise weight is bought from wrapped traders.
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: Assets,
+	) -> sp_std::result::Result<Assets, XcmError> {
+		let payment_asset = payment
+			.fungible_assets_iter()
+			.next()
+			.map(|asset| asset.id)
+			.ok_or(XcmError::NotHoldingFees)?;
+		if let Some(fee_asset) = self.fee_asset {
+			if fee_asset != payment_asset {
+				return Err(XcmError::TooExpensive)
+			}
+		}
+		let unused = self.traders.buy_weight(weight, payment)?;
+		self.fee_asset = Some(payment_asset);
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		self.traders.refund_weight(weight)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, IdentityFee};
+	use sp_runtime::{DispatchError, DispatchResult};
+	use std::cell::RefCell;
+
+	/// Whitelisted asset with price of native token
+	const DOT: u128 = 1;
+	/// Asset having pool with native token
+	const USDT: u128 = 2;
+	/// Whitelisted asset without price
+	const PHA: u128 = 3;
+	/// Asset which is not whitelisted
+	const ASTR: u128 = 4;
+	/// Asset having pool which fails to swap
+	const GLMR: u128 = 5;
+
+	thread_local! {
+		static REVENUE: RefCell<Vec<MultiAsset>> = RefCell::new(Vec::new());
+		static SWAPS: RefCell<Vec<(u128, u128, u128)>> = RefCell::new(Vec::new());
+	}
+
+	struct MockAssets;
+
+	impl AssetIdConverter for MockAssets {
+		fn convert_asset_id_to_location(asset_id: u128) -> Option<MultiLocation> {
+			Some(location(asset_id))
+		}
+
+		fn convert_location_to_asset_id(location: MultiLocation) -> Option<u128> {
+			match location {
+				MultiLocation { parents: 1, interior: X1(GeneralIndex(asset_id)) } =>
+					Some(asset_id),
+				_ => None,
+			}
+		}
+	}
+
+	impl WhitelistedTokenHandler for MockAssets {
+		fn check_whitelisted_token(asset_id: u128) -> bool {
+			asset_id == DOT || asset_id == PHA
+		}
+	}
+
+	impl AssetFeeRateProvider for MockAssets {
+		fn get_fee_rate(asset_id: u128) -> Option<AssetFeeRate> {
+			match asset_id {
+				DOT => Some(AssetFeeRate {
+					units_per_second: 2 * u128::from(WEIGHT_REF_TIME_PER_SECOND),
+					units_per_pov_byte: 1,
+				}),
+				PHA => Some(AssetFeeRate { units_per_second: 0, units_per_pov_byte: 0 }),
+				_ => None,
+			}
+		}
+	}
+
+	impl AssetPriceProvider for MockAssets {
+		fn get_native_token_price(asset_id: u128) -> Option<FixedU128> {
+			if asset_id == DOT {
+				Some(FixedU128::from_u32(2))
+			} else {
+				None
+			}
+		}
+	}
+
+	impl NativeFeeSwapper for MockAssets {
+		fn quote_native_fee(asset_id: u128, fee: u128) -> Option<u128> {
+			// Fee grows faster than linearly, like in constant-product pool
+			if asset_id == USDT || asset_id == GLMR {
+				Some(fee.saturating_mul(3).saturating_add(fee.saturating_mul(fee) / 100))
+			} else {
+				None
+			}
+		}
+
+		fn swap_into_native_fee(asset_id: u128, amount: u128, fee: u128) -> DispatchResult {
+			if asset_id == GLMR {
+				return Err(DispatchError::Other("Swap failed"))
+			}
+			SWAPS.with(|swaps| swaps.borrow_mut().push((asset_id, amount, fee)));
+			Ok(())
+		}
+	}
+
+	struct MockRevenue;
+
+	impl TakeRevenue for MockRevenue {
+		fn take_revenue(revenue: MultiAsset) {
+			REVENUE.with(|collected| collected.borrow_mut().push(revenue));
+		}
+	}
+
+	type Trader = ForeignAssetFeeHandler<
+		IdentityFee<u128>,
+		MockRevenue,
+		MockAssets,
+		MockAssets,
+		MockAssets,
+		MockAssets,
+	>;
+
+	type FixedRateTrader = FixedRateOfForeignAsset<MockAssets, MockAssets, MockRevenue>;
+
+	type Traders = SingleFeeAsset<(FixedRateTrader, Trader)>;
+
+	fn location(asset_id: u128) -> MultiLocation {
+		MultiLocation::new(1, X1(GeneralIndex(asset_id)))
+	}
+
+	fn asset(asset_id: u128, amount: u128) -> MultiAsset {
+		(location(asset_id), amount).into()
+	}
+
+	fn payment(asset_id: u128, amount: u128) -> Assets {
+		asset(asset_id, amount).into()
+	}
+
+	fn weight(ref_time: u64) -> Weight {
+		Weight::from_parts(ref_time, 0)
+	}
+
+	fn collected_revenue() -> Vec<MultiAsset> {
+		REVENUE.with(|collected| collected.borrow().clone())
+	}
+
+	fn executed_swaps() -> Vec<(u128, u128, u128)> {
+		SWAPS.with(|swaps| swaps.borrow().clone())
+	}
+
+	fn assert_ok(result: Result<Assets, XcmError>) {
+		assert!(result.is_ok(), "{:?}", result);
+	}
+
+	#[test]
+	fn test_buy_weight_accumulates_fee_of_multiple_calls() {
+		let mut trader = Trader::new();
+		let unused = trader.buy_weight(weight(100), payment(DOT, 1_000)).unwrap();
+		assert_eq!(unused, payment(DOT, 800));
+		let unused = trader.buy_weight(weight(50), unused).unwrap();
+		assert_eq!(unused, payment(DOT, 700));
+		assert_eq!(trader.refund_weight(weight(30)), Some(asset(DOT, 60)));
+		drop(trader);
+		assert_eq!(collected_revenue(), vec![asset(DOT, 240)]);
+	}
+
+	#[test]
+	fn test_refund_weight_never_exceeds_consumed_fee() {
+		let mut trader = Trader::new();
+		assert_ok(trader.buy_weight(weight(100), payment(DOT, 1_000)));
+		assert_eq!(trader.refund_weight(weight(1_000)), Some(asset(DOT, 200)));
+		assert_eq!(trader.refund_weight(weight(1)), None);
+		drop(trader);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_buy_weight_rejects_mixed_asset_payment() {
+		let mut trader = Trader::new();
+		assert_ok(trader.buy_weight(weight(100), payment(DOT, 1_000)));
+		assert_eq!(
+			trader.buy_weight(weight(100), payment(USDT, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		let mut mixed_payment = payment(DOT, 1_000);
+		mixed_payment.subsume(asset(USDT, 1_000));
+		assert_eq!(trader.buy_weight(weight(100), mixed_payment), Err(XcmError::TooExpensive));
+		// Rejected payments are not charged
+		drop(trader);
+		assert_eq!(collected_revenue(), vec![asset(DOT, 200)]);
+	}
+
+	#[test]
+	fn test_buy_weight_rejects_unsupported_payment() {
+		let mut trader = Trader::new();
+		assert_eq!(trader.buy_weight(weight(100), Assets::new()), Err(XcmError::NotHoldingFees));
+		assert_eq!(
+			trader.buy_weight(weight(100), MultiAsset::from((MultiLocation::here(), 1_000)).into()),
+			Err(XcmError::AssetNotFound)
+		);
+		assert_eq!(
+			trader.buy_weight(weight(100), payment(PHA, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		assert_eq!(
+			trader.buy_weight(weight(100), payment(ASTR, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		assert_eq!(trader.buy_weight(weight(100), payment(DOT, 199)), Err(XcmError::TooExpensive));
+		assert_eq!(trader.refund_weight(weight(100)), None);
+		drop(trader);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_fee_paid_in_pool_asset_is_swapped_on_drop() {
+		let mut trader = Trader::new();
+		// 3 * 100 + 100 * 100 / 100
+		let unused = trader.buy_weight(weight(100), payment(USDT, 2_000)).unwrap();
+		assert_eq!(unused, payment(USDT, 1_600));
+		// Whole fee is quoted again, 3 * 200 + 200 * 200 / 100 - 400
+		let unused = trader.buy_weight(weight(100), unused).unwrap();
+		assert_eq!(unused, payment(USDT, 1_000));
+		// Refund above the quote of remaining fee, 1_000 - (3 * 150 + 150 * 150 / 100)
+		assert_eq!(trader.refund_weight(weight(50)), Some(asset(USDT, 325)));
+		drop(trader);
+		assert_eq!(executed_swaps(), vec![(USDT, 675, 150)]);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_fee_is_collected_in_asset_if_swap_fails() {
+		let mut trader = Trader::new();
+		assert_ok(trader.buy_weight(weight(100), payment(GLMR, 1_000)));
+		drop(trader);
+		assert!(executed_swaps().is_empty());
+		assert_eq!(collected_revenue(), vec![asset(GLMR, 400)]);
+	}
+
+	#[test]
+	fn test_fixed_rate_buy_weight_rejects_unpriced_asset() {
+		let mut trader = FixedRateTrader::new();
+		assert_eq!(
+			trader.buy_weight(weight(100), MultiAsset::from((MultiLocation::here(), 1_000)).into()),
+			Err(XcmError::AssetNotFound)
+		);
+		assert_eq!(
+			trader.buy_weight(weight(100), payment(ASTR, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		assert_eq!(trader.refund_weight(weight(100)), None);
+		drop(trader);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_fixed_rate_buy_weight_rejects_zero_rate() {
+		let mut trader = FixedRateTrader::new();
+		assert_eq!(
+			trader.buy_weight(weight(100), payment(PHA, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		drop(trader);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_fixed_rate_buy_weight_rejects_insufficient_payment() {
+		let mut trader = FixedRateTrader::new();
+		// 2 * 100 for execution time and 1 * 10 for proof size
+		assert_eq!(
+			trader.buy_weight(Weight::from_parts(100, 10), payment(DOT, 209)),
+			Err(XcmError::TooExpensive)
+		);
+		let unused = trader.buy_weight(Weight::from_parts(100, 10), payment(DOT, 210)).unwrap();
+		assert_eq!(unused, Assets::new());
+		drop(trader);
+		assert_eq!(collected_revenue(), vec![asset(DOT, 210)]);
+	}
+
+	#[test]
+	fn test_fixed_rate_refund_weight_never_exceeds_consumed_fee() {
+		let mut trader = FixedRateTrader::new();
+		assert_ok(trader.buy_weight(weight(100), payment(DOT, 1_000)));
+		assert_eq!(trader.refund_weight(weight(1_000)), Some(asset(DOT, 200)));
+		assert_eq!(trader.refund_weight(weight(1)), None);
+		drop(trader);
+		assert!(collected_revenue().is_empty());
+	}
+
+	#[test]
+	fn test_fixed_rate_fee_is_sent_to_revenue_on_drop() {
+		let mut trader = FixedRateTrader::new();
+		let unused = trader.buy_weight(weight(100), payment(DOT, 1_000)).unwrap();
+		assert_eq!(unused, payment(DOT, 800));
+		let unused = trader.buy_weight(weight(50), unused).unwrap();
+		assert_eq!(unused, payment(DOT, 700));
+		assert_eq!(trader.refund_weight(weight(30)), Some(asset(DOT, 60)));
+		drop(trader);
+		assert_eq!(collected_revenue(), vec![asset(DOT, 240)]);
+	}
+
+	#[test]
+	fn test_single_fee_asset_rejects_payment_in_asset_of_other_trader() {
+		let mut traders = Traders::new();
+		// Rejected payment doesn't fix the fee asset
+		assert_eq!(
+			traders.buy_weight(weight(100), payment(ASTR, 1_000)),
+			Err(XcmError::TooExpensive)
+		);
+		// DOT is charged by the fixed rate trader
+		let unused = traders.buy_weight(weight(100), payment(DOT, 1_000)).unwrap();
+		assert_eq!(unused, payment(DOT, 800));
+		// USDT would be accepted by the other trader of the tuple
+		assert_eq!(
+			traders.buy_weight(weight(100), payment(USDT, 2_000)),
+			Err(XcmError::TooExpensive)
+		);
+		let unused = traders.buy_weight(weight(50), unused).unwrap();
+		assert_eq!(unused, payment(DOT, 700));
+		assert_eq!(traders.refund_weight(weight(30)), Some(asset(DOT, 60)));
+		drop(traders);
+		assert!(executed_swaps().is_empty());
+		assert_eq!(collected_revenue(), vec![asset(DOT, 240)]);
+	}
+}
@@ -13,15 +13,17 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
 // GNU General Public License for more details.
 
-//! Autogenerated weights for `xcm_helper`
+//! Weights for `xcm_helper`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-06-28, STEPS: `100`, REPEAT: `200`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Ubuntu-2204-jammy-amd64-base`, CPU: `Intel(R) Core(TM) i7-7700 CPU @ 3.60GHz`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 1024
+//! PROVISIONAL ESTIMATES, NOT BENCHMARK OUTPUT.
+//! Only `whitelist_token`, `remove_whitelisted_token` and `transfer_fee` were measured with the
+//! Substrate benchmark CLI (DATE: 2023-06-28, STEPS: `100`, REPEAT: `200`,
+//! CPU: `Intel(R) Core(TM) i7-7700 CPU @ 3.60GHz`).
+//! All other weights, and the storage reads, writes and proof sizes changed by bounded storage,
+//! are hand-written estimates derived from comparable extrinsics. Regenerate this file with the
+//! command below before relying on these figures.
 
-// Executed Command:
+// Command to regenerate:
 // ./parachain-polkadex-node
 // benchmark
 // pallet
@@ -47,12 +49,10 @@ use core::marker::PhantomData;
 /// Weight functions for `xcm_helper`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> crate::XcmHelperWeightInfo for WeightInfo<T> {
-    /// Storage: ParachainInfo ParachainId (r:1 w:0)
-    /// Proof: ParachainInfo ParachainId (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
     /// Storage: XcmHelper ParachainAssets (r:1 w:1)
-    /// Proof Skipped: XcmHelper ParachainAssets (max_values: None, max_size: None, mode: Measured)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
     /// Storage: XcmHelper WhitelistedTokens (r:1 w:1)
-    /// Proof Skipped: XcmHelper WhitelistedTokens (max_values: Some(1), max_size: None, mode: Measured)
+    /// Proof: XcmHelper WhitelistedTokens (max_values: Some(1), max_size: Some(801), added: 1296, mode: MaxEncodedLen)
     /// Storage: Assets Asset (r:1 w:1)
     /// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
     /// The range of component `b` is `[1, 1000]`.
@@ -60,31 +60,29 @@ impl<T: frame_system::Config> crate::XcmHelperWeightInfo for WeightInfo<T> {
     fn whitelist_token(_b: u32, ) -> Weight {
         // Proof Size summary in bytes:
         //  Measured:  `147`
-        //  Estimated: `3687`
+        //  Estimated: `4083`
         // Minimum execution time: 17_028_000 picoseconds.
         Weight::from_parts(17_753_020, 0)
-            .saturating_add(Weight::from_parts(0, 3687))
-            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(3))
     }
-    /// Storage: ParachainInfo ParachainId (r:1 w:0)
-    /// Proof: ParachainInfo ParachainId (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
     /// Storage: XcmHelper ParachainAssets (r:1 w:0)
-    /// Proof Skipped: XcmHelper ParachainAssets (max_values: None, max_size: None, mode: Measured)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
     /// Storage: XcmHelper WhitelistedTokens (r:1 w:1)
-    /// Proof Skipped: XcmHelper WhitelistedTokens (max_values: Some(1), max_size: None, mode: Measured)
+    /// Proof: XcmHelper WhitelistedTokens (max_values: Some(1), max_size: Some(801), added: 1296, mode: MaxEncodedLen)
     /// The range of component `b` is `[1, 1000]`.
     /// The range of component `b` is `[1, 1000]`.
     fn remove_whitelisted_token(b: u32, ) -> Weight {
         // Proof Size summary in bytes:
         //  Measured:  `225`
-        //  Estimated: `3690`
+        //  Estimated: `4083`
         // Minimum execution time: 13_237_000 picoseconds.
         Weight::from_parts(13_862_195, 0)
-            .saturating_add(Weight::from_parts(0, 3690))
+            .saturating_add(Weight::from_parts(0, 4083))
             // Standard Error: 1
             .saturating_add(Weight::from_parts(12, 0).saturating_mul(b.into()))
-            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(1))
     }
     /// Storage: System Account (r:2 w:2)
@@ -103,4 +101,354 @@ impl<T: frame_system::Config> crate::XcmHelperWeightInfo for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(2))
     }
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:2 w:2)
+    /// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+    /// Storage: System Account (r:2 w:2)
+    /// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+    fn transfer_fee_from_pot() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `226`
+        //  Estimated: `14135`
+        // Minimum execution time: 26_000_000 picoseconds.
+        Weight::from_parts(27_150_000, 0)
+            .saturating_add(Weight::from_parts(0, 14135))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalIndex (r:0 w:1)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn retry_failed_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `204`
+        //  Estimated: `632487`
+        // Minimum execution time: 14_107_000 picoseconds.
+        Weight::from_parts(14_651_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    /// Storage: XcmHelper RandomnessNonce (r:1 w:1)
+    /// Proof: XcmHelper RandomnessNonce (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: TheaMessageHandler OutgoingNonce (r:1 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingNonce (max_values: None, max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler ValidatorSetId (r:1 w:0)
+    /// Proof Skipped: TheaMessageHandler ValidatorSetId (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler OutgoingMessages (r:0 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingMessages (max_values: None, max_size: None, mode: Measured)
+    fn refund_failed_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `272`
+        //  Estimated: `632487`
+        // Minimum execution time: 26_310_000 picoseconds.
+        Weight::from_parts(27_188_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawalIndex (r:1 w:1)
+    /// Proof: XcmHelper FailedWithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn discard_failed_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `204`
+        //  Estimated: `632487`
+        // Minimum execution time: 10_472_000 picoseconds.
+        Weight::from_parts(10_915_000, 0)
+            .saturating_add(Weight::from_parts(0, 632487))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    /// Storage: XcmHelper PendingWithdrawals (r:1 w:1)
+    /// Proof: XcmHelper PendingWithdrawals (max_values: None, max_size: Some(615022), added: 617497, mode: Measured)
+    /// Storage: XcmHelper WithdrawalBacklogStart (r:1 w:0)
+    /// Proof: XcmHelper WithdrawalBacklogStart (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: XcmHelper OutboundPaused (r:1 w:0)
+    /// Proof: XcmHelper OutboundPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FailedWithdrawals (r:1 w:0)
+    /// Proof: XcmHelper FailedWithdrawals (max_values: None, max_size: Some(629022), added: 631497, mode: Measured)
+    /// Storage: XcmHelper FrozenAssets (r:1 w:0)
+    /// Proof: XcmHelper FrozenAssets (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+    /// Storage: XcmHelper NonFungibleInstances (r:1 w:0)
+    /// Proof: XcmHelper NonFungibleInstances (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AssetRateLimits (r:1 w:0)
+    /// Proof: XcmHelper AssetRateLimits (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalUsages (r:1 w:1)
+    /// Proof: XcmHelper WithdrawalUsages (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+    /// Storage: XcmHelper NonFungibleAssets (r:1 w:0)
+    /// Proof: XcmHelper NonFungibleAssets (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:1 w:1)
+    /// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+    /// Storage: System Account (r:1 w:1)
+    /// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+    /// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+    /// Proof Skipped: PolkadotXcm SupportedVersion (max_values: None, max_size: None, mode: Measured)
+    /// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+    /// Proof Skipped: PolkadotXcm VersionDiscoveryQueue (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+    /// Proof Skipped: PolkadotXcm SafeXcmVersion (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+    /// Proof Skipped: ParachainSystem HostConfiguration (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+    /// Proof Skipped: ParachainSystem PendingUpwardMessages (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: XcmHelper WithdrawalIndex (r:0 w:1)
+    /// Proof: XcmHelper WithdrawalIndex (max_values: None, max_size: Some(86), added: 2561, mode: MaxEncodedLen)
+    fn execute_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1093`
+        //  Estimated: `4083`
+        // Minimum execution time: 108_427_000 picoseconds.
+        Weight::from_parts(111_352_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(18))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+    /// Storage: XcmHelper AssetWithdrawalConfigs (r:0 w:1)
+    /// Proof: XcmHelper AssetWithdrawalConfigs (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    fn set_asset_withdrawal_config() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_052_000 picoseconds.
+        Weight::from_parts(8_391_000, 0)
+            .saturating_add(Weight::from_parts(0, 0))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper AssetRateLimits (r:0 w:1)
+    /// Proof: XcmHelper AssetRateLimits (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+    /// Storage: XcmHelper WithdrawalUsages (r:0 w:1)
+    /// Proof: XcmHelper WithdrawalUsages (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+    fn set_asset_rate_limit() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_317_000 picoseconds.
+        Weight::from_parts(8_645_000, 0)
+            .saturating_add(Weight::from_parts(0, 0))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    /// Storage: XcmHelper OutboundPaused (r:0 w:1)
+    /// Proof: XcmHelper OutboundPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn pause_bridge() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 6_912_000 picoseconds.
+        Weight::from_parts(7_204_000, 0)
+            .saturating_add(Weight::from_parts(0, 0))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper OutboundPaused (r:0 w:1)
+    /// Proof: XcmHelper OutboundPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn unpause_bridge() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 6_845_000 picoseconds.
+        Weight::from_parts(7_139_000, 0)
+            .saturating_add(Weight::from_parts(0, 0))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper FrozenAssets (r:0 w:1)
+    /// Proof: XcmHelper FrozenAssets (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+    fn freeze_asset() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `4083`
+        // Minimum execution time: 7_403_000 picoseconds.
+        Weight::from_parts(7_712_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper FrozenAssets (r:1 w:1)
+    /// Proof: XcmHelper FrozenAssets (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+    fn unfreeze_asset() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `3481`
+        // Minimum execution time: 7_288_000 picoseconds.
+        Weight::from_parts(7_596_000, 0)
+            .saturating_add(Weight::from_parts(0, 3481))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:1)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+    /// Storage: Assets Metadata (r:1 w:1)
+    /// Proof: Assets Metadata (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+    /// Storage: System Account (r:1 w:1)
+    /// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AssetRegistry (r:0 w:1)
+    /// Proof: XcmHelper AssetRegistry (max_values: None, max_size: Some(706), added: 3181, mode: MaxEncodedLen)
+    fn set_asset_metadata() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `312`
+        //  Estimated: `4083`
+        // Minimum execution time: 48_217_000 picoseconds.
+        Weight::from_parts(49_582_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper ParachainAssetIds (r:1 w:1)
+    /// Proof: XcmHelper ParachainAssetIds (max_values: None, max_size: Some(634), added: 3109, mode: MaxEncodedLen)
+    fn add_asset_alias() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `146`
+        //  Estimated: `4099`
+        // Minimum execution time: 15_311_000 picoseconds.
+        Weight::from_parts(15_874_000, 0)
+            .saturating_add(Weight::from_parts(0, 4099))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper ParachainAssetIds (r:1 w:1)
+    /// Proof: XcmHelper ParachainAssetIds (max_values: None, max_size: Some(634), added: 3109, mode: MaxEncodedLen)
+    fn remove_asset_alias() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `201`
+        //  Estimated: `4099`
+        // Minimum execution time: 16_027_000 picoseconds.
+        Weight::from_parts(16_512_000, 0)
+            .saturating_add(Weight::from_parts(0, 4099))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssetIds (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssetIds (max_values: None, max_size: Some(634), added: 3109, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AssetRegistry (r:1 w:1)
+    /// Proof: XcmHelper AssetRegistry (max_values: None, max_size: Some(706), added: 3181, mode: MaxEncodedLen)
+    /// Storage: XcmHelper ParachainAssets (r:0 w:1)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    fn set_canonical_asset_location() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `213`
+        //  Estimated: `4171`
+        // Minimum execution time: 17_436_000 picoseconds.
+        Weight::from_parts(18_003_000, 0)
+            .saturating_add(Weight::from_parts(0, 4171))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AbstractAssetLocations (r:0 w:1)
+    /// Proof: XcmHelper AbstractAssetLocations (max_values: None, max_size: Some(617), added: 3092, mode: MaxEncodedLen)
+    fn set_abstract_asset_location() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `142`
+        //  Estimated: `4083`
+        // Minimum execution time: 13_214_000 picoseconds.
+        Weight::from_parts(13_695_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper NonFungibleAssets (r:0 w:1)
+    /// Proof: XcmHelper NonFungibleAssets (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+    fn set_asset_non_fungible() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `142`
+        //  Estimated: `4083`
+        // Minimum execution time: 12_873_000 picoseconds.
+        Weight::from_parts(13_302_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AssetPrices (r:0 w:1)
+    /// Proof: XcmHelper AssetPrices (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+    fn set_asset_price() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `142`
+        //  Estimated: `4083`
+        // Minimum execution time: 12_958_000 picoseconds.
+        Weight::from_parts(13_417_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper ParachainAssets (r:1 w:0)
+    /// Proof: XcmHelper ParachainAssets (max_values: None, max_size: Some(618), added: 3093, mode: MaxEncodedLen)
+    /// Storage: XcmHelper AssetFeeRates (r:0 w:1)
+    /// Proof: XcmHelper AssetFeeRates (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+    fn set_asset_fee_rate() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `142`
+        //  Estimated: `4083`
+        // Minimum execution time: 13_105_000 picoseconds.
+        Weight::from_parts(13_586_000, 0)
+            .saturating_add(Weight::from_parts(0, 4083))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    /// Storage: XcmHelper UnmigratedWithdrawals (r:1 w:1)
+    /// Proof Skipped: XcmHelper UnmigratedWithdrawals (max_values: None, max_size: None, mode: Measured)
+    /// Storage: XcmHelper RandomnessNonce (r:1 w:1)
+    /// Proof: XcmHelper RandomnessNonce (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: TheaMessageHandler OutgoingNonce (r:1 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingNonce (max_values: None, max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler ValidatorSetId (r:1 w:0)
+    /// Proof Skipped: TheaMessageHandler ValidatorSetId (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler OutgoingMessages (r:0 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingMessages (max_values: None, max_size: None, mode: Measured)
+    fn refund_unmigrated_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `261`
+        //  Estimated: `3726`
+        // Minimum execution time: 24_871_000 picoseconds.
+        Weight::from_parts(25_640_000, 0)
+            .saturating_add(Weight::from_parts(0, 3726))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    /// Storage: XcmHelper RejectedWithdrawals (r:1 w:1)
+    /// Proof Skipped: XcmHelper RejectedWithdrawals (max_values: None, max_size: None, mode: Measured)
+    /// Storage: XcmHelper RandomnessNonce (r:1 w:1)
+    /// Proof: XcmHelper RandomnessNonce (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: TheaMessageHandler OutgoingNonce (r:1 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingNonce (max_values: None, max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler ValidatorSetId (r:1 w:0)
+    /// Proof Skipped: TheaMessageHandler ValidatorSetId (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: TheaMessageHandler OutgoingMessages (r:0 w:1)
+    /// Proof Skipped: TheaMessageHandler OutgoingMessages (max_values: None, max_size: None, mode: Measured)
+    fn refund_rejected_withdrawal() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `265`
+        //  Estimated: `3730`
+        // Minimum execution time: 25_012_000 picoseconds.
+        Weight::from_parts(25_794_000, 0)
+            .saturating_add(Weight::from_parts(0, 3730))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
 }
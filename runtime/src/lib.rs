@@ -17,7 +17,9 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, ConstU32, ConstU64, Get, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -121,8 +123,19 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	xcm_helper::migration::v1::MigrateToV1<Runtime>,
+	xcm_helper::migration::v2::MigrateToV2<Runtime>,
+	xcm_helper::migration::v3::MigrateToV3<Runtime>,
+	xcm_helper::migration::v4::MigrateToV4<Runtime>,
+	xcm_helper::migration::v5::MigrateToV5<Runtime>,
+	xcm_helper::migration::v6::MigrateToV6<Runtime>,
+);
+
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
 ///
@@ -472,10 +485,15 @@ impl pallet_sudo::Config for Runtime {
 
 parameter_types! {
 	pub const AssetHandlerPalletId: PalletId = PalletId(*b"XcmHandl");
+	pub const XcmFeePotId: PalletId = PalletId(*b"XcmFeePt");
+	pub XcmFeePotAccount: AccountId = XcmFeePotId::get().into_account_truncating();
 	pub const WithdrawalExecutionBlockDiff: u32 = 1000;
-	pub ParachainId: u32 = ParachainInfo::get().into();
 	pub const ParachainNetworkId: u8 = 1; // Our parachain's thea id is one.
 	pub const PolkadexAssetid: u128 = POLKADEX_NATIVE_ASSET_ID;
+	pub WithdrawalExecutionWeightLimit: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
+	pub const MaxPendingWithdrawalsPerBlock: u32 = 1000;
+	pub const MaxFailedWithdrawalsPerBlock: u32 = 1000;
+	pub const MaxWhitelistedTokens: u32 = 50;
 }
 
 impl xcm_helper::Config for Runtime {
@@ -488,9 +506,14 @@ impl xcm_helper::Config for Runtime {
 	type Executor = TheaMessageHandler;
 	type AssetHandlerPalletId = AssetHandlerPalletId;
 	type WithdrawalExecutionBlockDiff = WithdrawalExecutionBlockDiff;
-	type ParachainId = ParachainId;
 	type SubstrateNetworkId = ParachainNetworkId;
 	type NativeAssetId = PolkadexAssetid;
+	type WithdrawalExecutionWeightLimit = WithdrawalExecutionWeightLimit;
+	type MaxPendingWithdrawalsPerBlock = MaxPendingWithdrawalsPerBlock;
+	type MaxFailedWithdrawalsPerBlock = MaxFailedWithdrawalsPerBlock;
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+	type Swap = ();
+	type FeePotAccount = XcmFeePotAccount;
 	type WeightInfo = xcm_helper::weights::WeightInfo<Runtime>;
 }
 
@@ -701,6 +724,33 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_helper_runtime_api::XcmHelperApi<Block, AccountId, BlockNumber> for Runtime {
+		fn pending_withdrawals_by_block(block_no: BlockNumber) -> Vec<thea_primitives::types::Withdraw> {
+			XcmHelper::get_pending_withdrawals(block_no).into_iter().map(Into::into).collect()
+		}
+
+		fn pending_withdrawals_by_recipient(
+			recipient: AccountId,
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(BlockNumber, thea_primitives::types::Withdraw)>, Option<Vec<u8>>) {
+			XcmHelper::get_pending_withdrawals_by_recipient(&recipient, start_key, limit)
+		}
+
+		fn pending_withdrawal_by_id(
+			id: Vec<u8>,
+		) -> Option<(BlockNumber, thea_primitives::types::Withdraw)> {
+			XcmHelper::get_pending_withdrawal_by_id(&id)
+		}
+
+		fn failed_withdrawals(
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<xcm_helper::FailedWithdrawal<BlockNumber>>, Option<Vec<u8>>) {
+			XcmHelper::get_failed_withdrawals_page(start_key, limit)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		 fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> pallet_transaction_payment::RuntimeDispatchInfo<Balance> {
 			TransactionPayment::query_info(uxt, len)
@@ -17,12 +17,10 @@ use super::{
 	AccountId, Balances, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
 	RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
 };
-use crate::{AllPalletsWithSystem, Balance, PolkadexAssetid, XcmHelper};
-use core::marker::PhantomData;
+use crate::{AllPalletsWithSystem, Balance, XcmHelper};
 use frame_support::{
 	match_types, parameter_types,
 	traits::{Contains, Everything, Nothing},
-	weights::WeightToFee as WeightToFeeT,
 };
 use frame_system::EnsureRoot;
 
@@ -31,21 +29,18 @@ use orml_xcm_support::MultiNativeAsset;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
-use sp_core::{ConstU32, Get};
-use sp_runtime::{traits::Convert, SaturatedConversion};
+use sp_core::ConstU32;
+use sp_runtime::traits::Convert;
 use xcm::latest::{prelude::*, Weight as XCMWeight, Weight};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds,
 	IsConcrete, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeRevenue, TakeWeightCredit, UsingComponents,
+	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
 };
-use xcm_executor::{
-	traits::{WeightTrader, WithOriginFilter},
-	Assets, XcmExecutor,
-};
-use xcm_helper::{AssetIdConverter, WhitelistedTokenHandler};
+use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
+use xcm_helper::traders::{FixedRateOfForeignAsset, ForeignAssetFeeHandler, SingleFeeAsset};
 
 parameter_types! {
 	pub const RelayLocation: MultiLocation = MultiLocation::parent();
@@ -170,18 +165,16 @@ impl xcm_executor::Config for XcmConfig {
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
-	type Trader = (
+	// All fees of a message have to be paid in the same asset, whichever trader charges it
+	type Trader = SingleFeeAsset<(
 		// If the XCM message is paying the fees in PDEX ( the native ) then
 		// it will go to the author of the block as rewards
 		UsingComponents<WeightToFee, PdexLocation, AccountId, Balances, ToAuthor<Runtime>>,
-		ForeignAssetFeeHandler<
-			//TODO: Should we go for FixedRateOfForeignAsset
-			WeightToFee,
-			RevenueCollector,
-			XcmHelper,
-			XcmHelper,
-		>,
-	);
+		// Fees paid in foreign assets are collected into the fee pot of XcmHelper
+		ForeignAssetFeeHandler<WeightToFee, XcmHelper, XcmHelper, XcmHelper, XcmHelper, XcmHelper>,
+		// Assets without price are charged using fixed fee rates set by governance
+		FixedRateOfForeignAsset<XcmHelper, XcmHelper, XcmHelper>,
+	)>;
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
 	type AssetLocker = ();
@@ -282,94 +275,6 @@ impl orml_xtokens::Config for Runtime {
 	type UniversalLocation = UniversalLocation;
 }
 
-pub struct ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	/// Total used weight
-	weight: Weight,
-	/// Total consumed assets
-	consumed: u128,
-	/// Asset Id (as MultiLocation) and units per second for payment
-	asset_location_and_units_per_second: Option<(MultiLocation, u128)>,
-	_pd: PhantomData<(T, R, AC, WH)>,
-}
-
-impl<T, R, AC, WH> WeightTrader for ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	fn new() -> Self {
-		Self {
-			weight: Weight::zero(),
-			consumed: 0,
-			asset_location_and_units_per_second: None,
-			_pd: PhantomData,
-		}
-	}
-
-	/// NOTE: If the token is allowlisted by AMM pallet ( probably using governance )
-	/// then it will be allowed to execute for free even if the pool is not there.
-	/// If pool is not there and token is not present in allowlisted then it will be rejected.
-	fn buy_weight(
-		&mut self,
-		weight: Weight,
-		payment: Assets,
-	) -> sp_std::result::Result<Assets, XcmError> {
-		let _fee_in_native_token = T::weight_to_fee(&weight);
-		let payment_asset = payment.fungible_assets_iter().next().ok_or(XcmError::Trap(1000))?;
-		if let AssetId::Concrete(location) = payment_asset.id {
-			let foreign_currency_asset_id =
-				AC::convert_location_to_asset_id(location).ok_or(XcmError::Trap(1001))?;
-			let _path = [PolkadexAssetid::get(), foreign_currency_asset_id];
-			let (unused, expected_fee_in_foreign_currency) =
-				if WH::check_whitelisted_token(foreign_currency_asset_id) {
-					(payment, 0u128)
-				} else {
-					return Err(XcmError::Trap(1004))
-				};
-			self.weight = self.weight.saturating_add(weight);
-			if let Some((old_asset_location, _)) = self.asset_location_and_units_per_second {
-				if old_asset_location == location {
-					self.consumed = self
-						.consumed
-						.saturating_add((expected_fee_in_foreign_currency).saturated_into());
-				}
-			} else {
-				self.consumed = self
-					.consumed
-					.saturating_add((expected_fee_in_foreign_currency).saturated_into());
-				self.asset_location_and_units_per_second = Some((location, 0));
-			}
-			Ok(unused)
-		} else {
-			Err(XcmError::Trap(1005))
-		}
-	}
-}
-
-impl<T, R, AC, WH> Drop for ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	fn drop(&mut self) {
-		if let Some((asset_location, _)) = self.asset_location_and_units_per_second {
-			if self.consumed > 0 {
-				R::take_revenue((asset_location, self.consumed).into());
-			}
-		}
-	}
-}
-
 pub struct TypeConv;
 impl<Source: TryFrom<Dest> + Clone, Dest: TryFrom<Source> + Clone>
 	xcm_executor::traits::Convert<Source, Dest> for TypeConv
@@ -378,9 +283,3 @@ impl<Source: TryFrom<Dest> + Clone, Dest: TryFrom<Source> + Clone>
 		Dest::try_from(value.clone()).map_err(|_| value)
 	}
 }
-
-pub struct RevenueCollector;
-
-impl TakeRevenue for RevenueCollector {
-	fn take_revenue(_revenue: MultiAsset) {}
-}
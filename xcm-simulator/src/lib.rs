@@ -354,7 +354,7 @@ mod tests {
 			let assets_pallet_id: PalletId = frame_support::PalletId(*b"XcmHandl");
 			let pallet_id = assets_pallet_id.into_account_truncating();
 			mint_native_token(pallet_id);
-			XcmHelper::insert_pending_withdrawal(100, pending_withdrawal);
+			assert_ok!(XcmHelper::insert_pending_withdrawal(100, pending_withdrawal));
 			System::set_block_number(99);
 			run_to_block(100);
 			assert_eq!(
@@ -378,6 +378,7 @@ mod tests {
 			};
 			let destination: VersionedMultiLocation = destination.into();
 			// Register Asset Id
+			register_asset(asset_id);
 			let asset_id = XcmHelper::generate_asset_id_for_parachain(asset_id);
 			let pending_withdrawal = Withdraw {
 				id: Vec::new(),
@@ -389,7 +390,7 @@ mod tests {
 			};
 			create_dot_asset();
 			mint_native_token(sp_core::crypto::AccountId32::new([1; 32]));
-			XcmHelper::insert_pending_withdrawal(100, pending_withdrawal);
+			assert_ok!(XcmHelper::insert_pending_withdrawal(100, pending_withdrawal));
 			System::set_block_number(99);
 			run_to_block(100);
 		});
@@ -443,6 +444,87 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_non_fungible_deposit_from_relay_chain() {
+		MockNet::reset();
+		Relay::execute_with(|| {
+			use relay_chain::{RuntimeOrigin, Uniques};
+			assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 2, ALICE, false));
+			assert_ok!(Uniques::mint(RuntimeOrigin::signed(ALICE), 2, 69, child_account_id(1)));
+		});
+		ParaA::execute_with(|| {
+			register_non_fungible_asset(2);
+			// Sovereign account of ParaA sends the instance back to ParaA as reserve deposit
+			let message = Xcm(vec![
+				WithdrawAsset((GeneralIndex(2), 69u32).into()),
+				DepositReserveAsset {
+					assets: AllCounted(1).into(),
+					dest: Parachain(1).into(),
+					xcm: Xcm(vec![DepositAsset {
+						assets: AllCounted(1).into(),
+						beneficiary: (AccountId32 { id: ALICE.into(), network: None },).into(),
+					}]),
+				},
+			]);
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, Parent, message));
+		});
+		Relay::execute_with(|| {
+			assert_eq!(relay_chain::Uniques::owner(2, 69), Some(child_account_id(1)));
+		});
+		ParaA::execute_with(|| {
+			use parachain::{RuntimeEvent, System};
+			let collection = AssetId::Concrete((Parent, GeneralIndex(2)).into());
+			let collection_id = XcmHelper::generate_asset_id_for_parachain(collection);
+			let asset_id = XcmHelper::generate_non_fungible_instance_id(collection_id, Index(69));
+			assert_eq!(
+				XcmHelper::get_non_fungible_instance(asset_id),
+				Some((collection_id, Index(69)))
+			);
+			let nft = MultiAsset { id: collection, fun: NonFungible(Index(69)) };
+			let beneficiary: MultiLocation =
+				(AccountId32 { id: ALICE.into(), network: None },).into();
+			assert!(System::events().iter().any(|r| matches!(
+				&r.event,
+				RuntimeEvent::XcmHelper(xcm_helper::Event::AssetDeposited(who, what, id, _, _))
+					if **who == beneficiary && **what == nft && *id == asset_id
+			)));
+		});
+	}
+
+	#[test]
+	fn test_non_fungible_withdrawal_to_relay_chain() {
+		MockNet::reset();
+		Relay::execute_with(|| {
+			assert_eq!(relay_chain::Uniques::owner(1, 42), Some(child_account_id(1)));
+		});
+		ParaA::execute_with(|| {
+			let collection_id = register_non_fungible_asset(1);
+			// Instance is held by ParaA since genesis, record it as if it was deposited over XCM
+			let asset_id = XcmHelper::generate_non_fungible_instance_id(collection_id, Index(42));
+			xcm_helper::NonFungibleInstances::<parachain::Runtime>::insert(
+				asset_id,
+				(collection_id, Index(42)),
+			);
+			let destination: VersionedMultiLocation =
+				MultiLocation::new(1, X1(AccountId32 { network: None, id: ALICE.into() })).into();
+			let pending_withdrawal = Withdraw {
+				id: Vec::new(),
+				asset_id,
+				amount: 1,
+				destination: destination.encode(),
+				is_blocked: false,
+				extra: Vec::new(),
+			};
+			assert_ok!(XcmHelper::insert_pending_withdrawal(100, pending_withdrawal));
+			System::set_block_number(99);
+			run_to_block(100);
+			assert_eq!(XcmHelper::get_non_fungible_instance(asset_id), None);
+		});
+		Relay::execute_with(|| {
+			assert_eq!(relay_chain::Uniques::owner(1, 42), Some(ALICE));
+		});
+	}
+
 	use crate::parachain::{AssetHandlerPalletId, Assets};
 	fn mint_dot_token(account: AccountId) {
 		use frame_support::traits::fungibles::Mutate;
@@ -479,6 +561,7 @@ mod tests {
 			ALICE,
 			1
 		));
+		register_asset(asset);
 	}
 
 	fn mint_non_native_token(account: AccountId) {
@@ -501,6 +584,7 @@ mod tests {
 			ALICE,
 			1
 		));
+		register_asset(asset);
 	}
 
 	fn create_parachain_a_asset() {
@@ -512,6 +596,27 @@ mod tests {
 			ALICE,
 			1
 		));
+		register_asset(AssetId::Concrete(MultiLocation::new(1, X1(Parachain(1)))));
+	}
+
+	/// Registers asset mapping through governance, as deposits no longer store it. Asset is
+	/// priced 1:1 with native token, so it can pay for XCM execution.
+	fn register_asset(asset: AssetId) {
+		assert_ok!(XcmHelper::whitelist_token(RuntimeOrigin::root(), asset));
+		assert_ok!(XcmHelper::set_asset_price(
+			RuntimeOrigin::root(),
+			XcmHelper::generate_asset_id_for_parachain(asset),
+			Some(FixedU128::from_u32(1))
+		));
+	}
+
+	/// Registers collection of relay chain as non-fungible asset
+	fn register_non_fungible_asset(collection: u128) -> u128 {
+		let collection = AssetId::Concrete((Parent, GeneralIndex(collection)).into());
+		register_asset(collection);
+		let asset_id = XcmHelper::generate_asset_id_for_parachain(collection);
+		assert_ok!(XcmHelper::set_asset_non_fungible(RuntimeOrigin::root(), asset_id, true));
+		asset_id
 	}
 
 	fn create_dot_asset() {
@@ -524,9 +629,10 @@ mod tests {
 			ALICE,
 			1
 		));
+		register_asset(asset);
 	}
 	use frame_support::traits::{OnFinalize, OnInitialize};
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128};
 	use thea_primitives::types::Withdraw;
 
 	pub fn run_to_block(n: u64) {
@@ -27,11 +27,10 @@ use frame_support::{
 	},
 	weights::{
 		constants::{ExtrinsicBaseWeight, WEIGHT_REF_TIME_PER_SECOND},
-		Weight, WeightToFee as WeightToFeeT, WeightToFeeCoefficients, WeightToFeePolynomial,
+		Weight, WeightToFeeCoefficients, WeightToFeePolynomial,
 	},
 	PalletId,
 };
-use std::marker::PhantomData;
 
 use frame_system::{EnsureRoot, EnsureSigned};
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key};
@@ -43,8 +42,8 @@ use polkadot_parachain::primitives::{
 use sp_core::{ConstU32, H256};
 use sp_runtime::{
 	testing::Header,
-	traits::{Convert, Hash, IdentityLookup},
-	AccountId32, SaturatedConversion,
+	traits::{AccountIdConversion, Convert, Hash, IdentityLookup},
+	AccountId32,
 };
 use sp_std::prelude::*;
 use thea::ecdsa::{AuthorityId, AuthoritySignature};
@@ -54,13 +53,10 @@ use xcm_builder::{
 	Account32Hash, AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin,
 	FixedRateOfFungible, FixedWeightBounds, NativeAsset, ParentIsPreset,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeRevenue,
+	SovereignSignedViaLocation,
 };
-use xcm_executor::{
-	traits::{Convert as XCMConvert, WeightTrader},
-	Assets as AssetsXcm, Config, XcmExecutor,
-};
-use xcm_helper::{AssetIdConverter, WhitelistedTokenHandler};
+use xcm_executor::{Config, XcmExecutor};
+use xcm_helper::traders::{FixedRateOfForeignAsset, ForeignAssetFeeHandler, SingleFeeAsset};
 
 pub type SovereignAccountOf = (
 	SiblingParachainConvertsVia<Sibling, AccountId>,
@@ -203,10 +199,11 @@ impl Config for XcmConfig {
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
-	type Trader = (
+	type Trader = SingleFeeAsset<(
 		FixedRateOfFungible<KsmPerSecondPerByte, ()>,
-		ForeignAssetFeeHandler<WeightToFee, RevenueCollector, XcmHelper, XcmHelper>,
-	);
+		ForeignAssetFeeHandler<WeightToFee, XcmHelper, XcmHelper, XcmHelper, XcmHelper, XcmHelper>,
+		FixedRateOfForeignAsset<XcmHelper, XcmHelper, XcmHelper>,
+	)>;
 	type ResponseHandler = ();
 	type AssetTrap = ();
 	type AssetLocker = ();
@@ -433,10 +430,15 @@ impl pallet_assets::Config for Runtime {
 
 parameter_types! {
 	pub const AssetHandlerPalletId: PalletId = PalletId(*b"XcmHandl");
+	pub const XcmFeePotId: PalletId = PalletId(*b"XcmFeePt");
+	pub XcmFeePotAccount: AccountId = XcmFeePotId::get().into_account_truncating();
 	pub const WithdrawalExecutionBlockDiff: u32 = 1000;
-	pub ParachainId: u32 = MsgQueue::parachain_id().into();
 	pub const ParachainNetworkId: u8 = 1; // Our parachain's thea id is one.
 	pub const PolkadexAssetid: u128 = 1;
+	pub const WithdrawalExecutionWeightLimit: Weight = Weight::MAX;
+	pub const MaxPendingWithdrawalsPerBlock: u32 = 100;
+	pub const MaxFailedWithdrawalsPerBlock: u32 = 100;
+	pub const MaxWhitelistedTokens: u32 = 50;
 }
 
 impl xcm_helper::Config for Runtime {
@@ -449,9 +451,14 @@ impl xcm_helper::Config for Runtime {
 	type Executor = Executor;
 	type AssetHandlerPalletId = AssetHandlerPalletId;
 	type WithdrawalExecutionBlockDiff = WithdrawalExecutionBlockDiff;
-	type ParachainId = ParachainId;
 	type SubstrateNetworkId = ParachainNetworkId;
 	type NativeAssetId = PolkadexAssetid;
+	type WithdrawalExecutionWeightLimit = WithdrawalExecutionWeightLimit;
+	type MaxPendingWithdrawalsPerBlock = MaxPendingWithdrawalsPerBlock;
+	type MaxFailedWithdrawalsPerBlock = MaxFailedWithdrawalsPerBlock;
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+	type Swap = ();
+	type FeePotAccount = XcmFeePotAccount;
 	type WeightInfo = xcm_helper::weights::WeightInfo<Runtime>;
 }
 
@@ -521,94 +528,6 @@ construct_runtime!(
 	}
 );
 
-pub struct ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	/// Total used weight
-	weight: Weight,
-	/// Total consumed assets
-	consumed: u128,
-	/// Asset Id (as MultiLocation) and units per second for payment
-	asset_location_and_units_per_second: Option<(MultiLocation, u128)>,
-	_pd: PhantomData<(T, R, AC, WH)>,
-}
-
-impl<T, R, AC, WH> WeightTrader for ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	fn new() -> Self {
-		Self {
-			weight: Weight::zero(),
-			consumed: 0,
-			asset_location_and_units_per_second: None,
-			_pd: PhantomData,
-		}
-	}
-
-	/// NOTE: If the token is allowlisted by AMM pallet ( probably using governance )
-	/// then it will be allowed to execute for free even if the pool is not there.
-	/// If pool is not there and token is not present in allowlisted then it will be rejected.
-	fn buy_weight(
-		&mut self,
-		weight: Weight,
-		payment: AssetsXcm,
-	) -> sp_std::result::Result<AssetsXcm, XcmError> {
-		let _fee_in_native_token = T::weight_to_fee(&weight);
-		let payment_asset = payment.fungible_assets_iter().next().ok_or(XcmError::Trap(1000))?;
-		if let AssetId::Concrete(location) = payment_asset.id {
-			let foreign_currency_asset_id =
-				AC::convert_location_to_asset_id(location).ok_or(XcmError::Trap(1001))?;
-			let _path = [PolkadexAssetid::get(), foreign_currency_asset_id];
-			let (unused, expected_fee_in_foreign_currency) =
-				if WH::check_whitelisted_token(foreign_currency_asset_id) {
-					(payment, 0u128)
-				} else {
-					return Err(XcmError::Trap(1004))
-				};
-			self.weight = self.weight.saturating_add(weight);
-			if let Some((old_asset_location, _)) = self.asset_location_and_units_per_second {
-				if old_asset_location == location {
-					self.consumed = self
-						.consumed
-						.saturating_add((expected_fee_in_foreign_currency).saturated_into());
-				}
-			} else {
-				self.consumed = self
-					.consumed
-					.saturating_add((expected_fee_in_foreign_currency).saturated_into());
-				self.asset_location_and_units_per_second = Some((location, 0));
-			}
-			Ok(unused)
-		} else {
-			Err(XcmError::Trap(1005))
-		}
-	}
-}
-
-impl<T, R, AC, WH> Drop for ForeignAssetFeeHandler<T, R, AC, WH>
-where
-	T: WeightToFeeT<Balance = u128>,
-	R: TakeRevenue,
-	AC: AssetIdConverter,
-	WH: WhitelistedTokenHandler,
-{
-	fn drop(&mut self) {
-		if let Some((asset_location, _)) = self.asset_location_and_units_per_second {
-			if self.consumed > 0 {
-				R::take_revenue((asset_location, self.consumed).into());
-			}
-		}
-	}
-}
-
 pub struct TypeConv;
 impl<Source: TryFrom<Dest> + Clone, Dest: TryFrom<Source> + Clone>
 	xcm_executor::traits::Convert<Source, Dest> for TypeConv
@@ -618,12 +537,6 @@ impl<Source: TryFrom<Dest> + Clone, Dest: TryFrom<Source> + Clone>
 	}
 }
 
-pub struct RevenueCollector;
-
-impl TakeRevenue for RevenueCollector {
-	fn take_revenue(_revenue: MultiAsset) {}
-}
-
 use frame_support::weights::WeightToFeeCoefficient;
 use smallvec::smallvec;
 use sp_runtime::Perbill;